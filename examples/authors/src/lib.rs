@@ -53,6 +53,64 @@ mod tests {
         assert_eq!(authors_list.len(), 0);
     }
 
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn attributes_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+
+        let create_author = queries::CreateAuthor {
+            name: "FOO".into(),
+            ..Default::default()
+        };
+        let created = create_author.clone().query_one(&ctx.client).await.unwrap();
+        assert_eq!(create_author.bio, None);
+
+        let mut unique = std::collections::HashSet::new();
+        for _ in 0..2 {
+            let author = queries::get_author(&ctx.client, created.id)
+                .await
+                .unwrap()
+                .unwrap();
+            unique.insert(author);
+        }
+        assert_eq!(unique.len(), 1);
+    }
+
+    async fn query_as<T: queries::FromPgRow>(
+        client: &tokio_postgres::Client,
+        sql: &str,
+    ) -> Result<Vec<T>, tokio_postgres::Error> {
+        client
+            .query(sql, &[])
+            .await?
+            .iter()
+            .map(T::from_pg_row)
+            .collect()
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn from_pg_row_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+        queries::create_author(&ctx.client, "FOO", None)
+            .await
+            .unwrap();
+
+        let authors = query_as::<queries::ListAuthorsRow>(&ctx.client, "SELECT * FROM authors")
+            .await
+            .unwrap();
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].name, "FOO");
+
+        let row = ctx
+            .client
+            .query_one("SELECT * FROM authors", &[])
+            .await
+            .unwrap();
+        let author = queries::GetAuthorRow::try_from(&row).unwrap();
+        assert_eq!(author.id, authors[0].id);
+    }
+
     #[test_context(PgTokioTestContext)]
     #[tokio::test]
     async fn nullable_copy_type_state_works(ctx: &mut PgTokioTestContext) {
@@ -108,6 +166,190 @@ mod tests {
         println!("✅ All nullable Copy type state pattern tests passed!");
     }
 
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn list_authors_stream_works(ctx: &mut PgTokioTestContext) {
+        use futures_util::TryStreamExt as _;
+
+        migrate_db(&ctx.client).await;
+        for name in ["FOO", "BAR", "BAZ"] {
+            queries::create_author(&ctx.client, name, None)
+                .await
+                .unwrap();
+        }
+
+        let stream = queries::list_authors_stream(&ctx.client).await.unwrap();
+        let authors = std::pin::pin!(stream)
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap();
+        assert_eq!(authors.len(), 3);
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn list_authors_cursor_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+        for name in ["A", "B", "C", "D", "E"] {
+            queries::create_author(&ctx.client, name, None)
+                .await
+                .unwrap();
+        }
+
+        let transaction = ctx.client.transaction().await.unwrap();
+        let batch_size = std::num::NonZeroU32::new(2).unwrap();
+        let mut cursor = queries::list_authors_cursor(&transaction, batch_size)
+            .await
+            .unwrap();
+        let mut names = Vec::new();
+        loop {
+            let batch = cursor.next_batch().await.unwrap();
+            if batch.is_empty() {
+                break;
+            }
+            assert!(batch.len() <= 2);
+            names.extend(batch.into_iter().map(|author| author.name));
+        }
+        assert_eq!(names.len(), 5);
+        drop(cursor);
+        transaction.commit().await.unwrap();
+    }
+
+    type SpanFields = Vec<(String, String)>;
+
+    /// Collects span names and recorded fields
+    #[derive(Default, Clone)]
+    struct SpanCollector {
+        spans: std::sync::Arc<std::sync::Mutex<Vec<(String, SpanFields)>>>,
+    }
+
+    struct FieldCollector<'a>(&'a mut SpanFields);
+
+    impl tracing::field::Visit for FieldCollector<'_> {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+
+    impl tracing::Subscriber for SpanCollector {
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            let mut spans = self.spans.lock().unwrap();
+            let mut fields = Vec::new();
+            span.record(&mut FieldCollector(&mut fields));
+            spans.push((span.metadata().name().to_string(), fields));
+            tracing::span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &tracing::span::Id, values: &tracing::span::Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+            values.record(&mut FieldCollector(fields));
+        }
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, _: &tracing::Event<'_>) {}
+
+        fn enter(&self, _: &tracing::span::Id) {}
+
+        fn exit(&self, _: &tracing::span::Id) {}
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn tracing_spans_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+
+        let collector = SpanCollector::default();
+        let _guard = tracing::subscriber::set_default(collector.clone());
+
+        queries::create_author(&ctx.client, "FOO", None)
+            .await
+            .unwrap();
+        let _ = queries::list_authors(&ctx.client).await.unwrap();
+        queries::delete_author(&ctx.client, -1).await.unwrap();
+
+        let spans = collector.spans.lock().unwrap();
+        let names = spans
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["CreateAuthor", "ListAuthors", "DeleteAuthor"]);
+
+        let field = |span: usize, name: &str| {
+            spans[span]
+                .1
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(field(0, "db.system").as_deref(), Some("\"postgresql\""));
+        assert_eq!(field(0, "db.operation").as_deref(), Some("\"INSERT\""));
+        assert!(field(0, "db.statement")
+            .unwrap()
+            .contains("INSERT INTO authors"));
+        assert_eq!(field(0, "db.response.returned_rows").as_deref(), Some("1"));
+        assert_eq!(field(1, "db.response.returned_rows").as_deref(), Some("1"));
+        assert_eq!(field(2, "db.rows_affected").as_deref(), Some("0"));
+    }
+
+    async fn rename_author(
+        querier: &impl queries::Querier,
+        id: i64,
+        name: &str,
+    ) -> Option<queries::CreateAuthorRow> {
+        let author = querier.get_author(id).await.ok()??;
+        querier
+            .create_author(name, author.bio.as_deref())
+            .await
+            .ok()?
+    }
+
+    #[tokio::test]
+    async fn mock_querier_works() {
+        let mock = queries::MockQuerier::new();
+        mock.push_get_author(Ok(Some(queries::GetAuthorRow {
+            id: 1,
+            name: "FOO".to_owned(),
+            bio: Some("BAR".to_owned()),
+            age: None,
+            is_active: None,
+        })))
+        .push_create_author(Ok(Some(queries::CreateAuthorRow {
+            id: 2,
+            name: "BAZ".to_owned(),
+            bio: Some("BAR".to_owned()),
+            age: None,
+            is_active: None,
+        })));
+
+        let created = rename_author(&mock, 1, "BAZ").await.unwrap();
+        assert_eq!(created.id, 2);
+
+        let calls = mock.take_calls();
+        assert_eq!(
+            calls,
+            [
+                queries::MockQuerierCall::GetAuthor(queries::GetAuthor { id: 1 }),
+                queries::MockQuerierCall::CreateAuthor(queries::CreateAuthor {
+                    name: "BAZ".into(),
+                    bio: Some("BAR".into()),
+                }),
+            ]
+        );
+        assert_eq!(calls.clone(), calls);
+        assert_ne!(calls[0], queries::MockQuerierCall::ListAuthors);
+
+        mock.push_list_authors(Err(queries::MockQuerierError("boom".to_owned())));
+        let err = queries::Querier::list_authors(&mock).await.unwrap_err();
+        assert_eq!(err.to_string(), "boom");
+    }
+
     #[test]
     fn zero_cost_abstraction_verification() {
         // ゼロコスト抽象化の基本確認
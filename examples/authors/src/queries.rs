@@ -1,10 +1,36 @@
 //! Code generated by sqlc. SHOULD NOT EDIT.
 //! sqlc version: v1.28.0
 //! sqlc-rust-postgres version: v0.1.4
+/// Server-side cursor yielding the rows of a `:many` query in batches
+pub struct RowCursor<'t, 'c, T> {
+    transaction: &'t tokio_postgres::Transaction<'c>,
+    portal: tokio_postgres::Portal,
+    batch_size: i32,
+    done: bool,
+    from_row: fn(&tokio_postgres::Row) -> Result<T, tokio_postgres::Error>,
+}
+impl<'t, 'c, T> RowCursor<'t, 'c, T> {
+    /// Fetches the next batch of rows; an empty batch means the cursor is exhausted
+    pub async fn next_batch(&mut self) -> Result<Vec<T>, tokio_postgres::Error> {
+        if self.done {
+            return Ok(Vec::new());
+        }
+        let rows = self
+            .transaction
+            .query_portal(&self.portal, self.batch_size)
+            .await?;
+        self.done = rows.len() < self.batch_size as usize;
+        rows.iter().map(self.from_row).collect()
+    }
+}
+/// Conversion of a `Row` into a generated row struct
+pub trait FromPgRow: Sized {
+    fn from_pg_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error>;
+}
 pub const GET_AUTHOR: &str = r#"-- name: GetAuthor :one
 SELECT id, name, bio, age, is_active FROM authors
 WHERE id = $1 LIMIT 1"#;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetAuthorRow {
     pub id: i64,
     pub name: String,
@@ -23,6 +49,25 @@ impl GetAuthorRow {
         })
     }
 }
+impl FromPgRow for GetAuthorRow {
+    fn from_pg_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Self::from_row(row)
+    }
+}
+impl TryFrom<&tokio_postgres::Row> for GetAuthorRow {
+    type Error = tokio_postgres::Error;
+    fn try_from(row: &tokio_postgres::Row) -> Result<Self, Self::Error> {
+        Self::from_row(row)
+    }
+}
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, name, bio, age, is_active FROM authors
+/// WHERE id = $1 LIMIT 1
+/// ```
+///
+/// </details>
 pub async fn get_author(
     client: &impl tokio_postgres::GenericClient,
     id: i64,
@@ -44,18 +89,64 @@ impl GetAuthor {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<GetAuthorRow, tokio_postgres::Error> {
-        let row = client.query_one(Self::QUERY, &[&self.id]).await?;
-        GetAuthorRow::from_row(&row)
+        let span = tracing::info_span!(
+            "GetAuthor",
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            db.statement = Self::QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let result = tracing::Instrument::instrument(
+            async {
+                let row = client.query_one(Self::QUERY, &[&self.id]).await?;
+                GetAuthorRow::from_row(&row)
+            },
+            span.clone(),
+        )
+        .await;
+        match &result {
+            Ok(_value) => {
+                span.record("db.response.returned_rows", 1u64);
+            }
+            Err(err) => {
+                span.record("error", tracing::field::display(err));
+            }
+        }
+        result
     }
     pub async fn query_opt(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Option<GetAuthorRow>, tokio_postgres::Error> {
-        let row = client.query_opt(Self::QUERY, &[&self.id]).await?;
-        match row {
-            Some(ref row) => Ok(Some(GetAuthorRow::from_row(row)?)),
-            None => Ok(None),
+        let span = tracing::info_span!(
+            "GetAuthor",
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            db.statement = Self::QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let result = tracing::Instrument::instrument(
+            async {
+                let row = client.query_opt(Self::QUERY, &[&self.id]).await?;
+                match row {
+                    Some(row) => Ok(Some(GetAuthorRow::from_row(&row)?)),
+                    None => Ok(None),
+                }
+            },
+            span.clone(),
+        )
+        .await;
+        match &result {
+            Ok(value) => {
+                span.record("db.response.returned_rows", u64::from(value.is_some()));
+            }
+            Err(err) => {
+                span.record("error", tracing::field::display(err));
+            }
         }
+        result
     }
 }
 #[derive(Debug)]
@@ -108,14 +199,99 @@ impl ListAuthorsRow {
         })
     }
 }
+impl FromPgRow for ListAuthorsRow {
+    fn from_pg_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Self::from_row(row)
+    }
+}
+impl TryFrom<&tokio_postgres::Row> for ListAuthorsRow {
+    type Error = tokio_postgres::Error;
+    fn try_from(row: &tokio_postgres::Row) -> Result<Self, Self::Error> {
+        Self::from_row(row)
+    }
+}
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, name, bio, age, is_active FROM authors
+/// ORDER BY name
+/// ```
+///
+/// </details>
 pub async fn list_authors(
     client: &impl tokio_postgres::GenericClient,
 ) -> Result<
     impl Iterator<Item = Result<ListAuthorsRow, tokio_postgres::Error>>,
     tokio_postgres::Error,
 > {
-    let rows = client.query(LIST_AUTHORS, &[]).await?;
-    Ok(rows.into_iter().map(|r| ListAuthorsRow::from_row(&r)))
+    let span = tracing::info_span!(
+        "ListAuthors",
+        db.system = "postgresql",
+        db.operation = "SELECT",
+        db.statement = LIST_AUTHORS,
+        db.response.returned_rows = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+    let result = tracing::Instrument::instrument(
+        async {
+            let rows = client.query(LIST_AUTHORS, &[]).await?;
+            Ok(rows.into_iter().map(|r| ListAuthorsRow::from_row(&r)))
+        },
+        span.clone(),
+    )
+    .await;
+    match &result {
+        Ok(value) => {
+            span.record("db.response.returned_rows", value.len() as u64);
+        }
+        Err(err) => {
+            span.record("error", tracing::field::display(err));
+        }
+    }
+    result
+}
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, name, bio, age, is_active FROM authors
+/// ORDER BY name
+/// ```
+///
+/// </details>
+pub async fn list_authors_stream(
+    client: &impl tokio_postgres::GenericClient,
+) -> Result<
+    impl futures_util::Stream<Item = Result<ListAuthorsRow, tokio_postgres::Error>>,
+    tokio_postgres::Error,
+> {
+    let params: &[&(dyn tokio_postgres::types::ToSql + Sync)] = &[];
+    let rows = client
+        .query_raw(LIST_AUTHORS, params.iter().copied())
+        .await?;
+    Ok(futures_util::StreamExt::map(rows, |row| {
+        row.and_then(|row| ListAuthorsRow::from_row(&row))
+    }))
+}
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, name, bio, age, is_active FROM authors
+/// ORDER BY name
+/// ```
+///
+/// </details>
+pub async fn list_authors_cursor<'t, 'c>(
+    client: &'t tokio_postgres::Transaction<'c>,
+    batch_size: std::num::NonZeroU32,
+) -> Result<RowCursor<'t, 'c, ListAuthorsRow>, tokio_postgres::Error> {
+    let portal = client.bind(LIST_AUTHORS, &[]).await?;
+    Ok(RowCursor {
+        transaction: client,
+        portal,
+        batch_size: i32::try_from(batch_size.get()).unwrap_or(i32::MAX),
+        done: false,
+        from_row: ListAuthorsRow::from_row,
+    })
 }
 pub const CREATE_AUTHOR: &str = r#"-- name: CreateAuthor :one
 INSERT INTO authors (
@@ -143,6 +319,29 @@ impl CreateAuthorRow {
         })
     }
 }
+impl FromPgRow for CreateAuthorRow {
+    fn from_pg_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Self::from_row(row)
+    }
+}
+impl TryFrom<&tokio_postgres::Row> for CreateAuthorRow {
+    type Error = tokio_postgres::Error;
+    fn try_from(row: &tokio_postgres::Row) -> Result<Self, Self::Error> {
+        Self::from_row(row)
+    }
+}
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// INSERT INTO authors (
+///          name, bio
+/// ) VALUES (
+///  $1, $2
+/// )
+/// RETURNING id, name, bio, age, is_active
+/// ```
+///
+/// </details>
 pub async fn create_author(
     client: &impl tokio_postgres::GenericClient,
     name: &str,
@@ -154,7 +353,8 @@ pub async fn create_author(
     };
     query_struct.query_opt(client).await
 }
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(Default))]
 pub struct CreateAuthor<'a> {
     pub name: std::borrow::Cow<'a, str>,
     pub bio: Option<std::borrow::Cow<'a, str>>,
@@ -173,22 +373,68 @@ impl<'a> CreateAuthor<'a> {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<CreateAuthorRow, tokio_postgres::Error> {
-        let row = client
-            .query_one(Self::QUERY, &[&self.name.as_ref(), &self.bio.as_deref()])
-            .await?;
-        CreateAuthorRow::from_row(&row)
+        let span = tracing::info_span!(
+            "CreateAuthor",
+            db.system = "postgresql",
+            db.operation = "INSERT",
+            db.statement = Self::QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let result = tracing::Instrument::instrument(
+            async {
+                let row = client
+                    .query_one(Self::QUERY, &[&self.name.as_ref(), &self.bio.as_deref()])
+                    .await?;
+                CreateAuthorRow::from_row(&row)
+            },
+            span.clone(),
+        )
+        .await;
+        match &result {
+            Ok(_value) => {
+                span.record("db.response.returned_rows", 1u64);
+            }
+            Err(err) => {
+                span.record("error", tracing::field::display(err));
+            }
+        }
+        result
     }
     pub async fn query_opt(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Option<CreateAuthorRow>, tokio_postgres::Error> {
-        let row = client
-            .query_opt(Self::QUERY, &[&self.name.as_ref(), &self.bio.as_deref()])
-            .await?;
-        match row {
-            Some(ref row) => Ok(Some(CreateAuthorRow::from_row(row)?)),
-            None => Ok(None),
+        let span = tracing::info_span!(
+            "CreateAuthor",
+            db.system = "postgresql",
+            db.operation = "INSERT",
+            db.statement = Self::QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let result = tracing::Instrument::instrument(
+            async {
+                let row = client
+                    .query_opt(Self::QUERY, &[&self.name.as_ref(), &self.bio.as_deref()])
+                    .await?;
+                match row {
+                    Some(row) => Ok(Some(CreateAuthorRow::from_row(&row)?)),
+                    None => Ok(None),
+                }
+            },
+            span.clone(),
+        )
+        .await;
+        match &result {
+            Ok(value) => {
+                span.record("db.response.returned_rows", u64::from(value.is_some()));
+            }
+            Err(err) => {
+                span.record("error", tracing::field::display(err));
+            }
         }
+        result
     }
 }
 #[derive(Debug, Default)]
@@ -226,11 +472,40 @@ impl<'a> CreateAuthorBuilder<'a> {
 pub const DELETE_AUTHOR: &str = r#"-- name: DeleteAuthor :exec
 DELETE FROM authors
 WHERE id = $1"#;
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// DELETE FROM authors
+/// WHERE id = $1
+/// ```
+///
+/// </details>
 pub async fn delete_author(
     client: &impl tokio_postgres::GenericClient,
     id: i64,
 ) -> Result<u64, tokio_postgres::Error> {
-    client.execute(DELETE_AUTHOR, &[&id]).await
+    let span = tracing::info_span!(
+        "DeleteAuthor",
+        db.system = "postgresql",
+        db.operation = "DELETE",
+        db.statement = DELETE_AUTHOR,
+        db.rows_affected = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+    let result = tracing::Instrument::instrument(
+        async { client.execute(DELETE_AUTHOR, &[&id]).await },
+        span.clone(),
+    )
+    .await;
+    match &result {
+        Ok(value) => {
+            span.record("db.rows_affected", *value);
+        }
+        Err(err) => {
+            span.record("error", tracing::field::display(err));
+        }
+    }
+    result
 }
 #[derive(Debug)]
 pub struct DeleteAuthor {
@@ -246,7 +521,28 @@ impl DeleteAuthor {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<u64, tokio_postgres::Error> {
-        client.execute(Self::QUERY, &[&self.id]).await
+        let span = tracing::info_span!(
+            "DeleteAuthor",
+            db.system = "postgresql",
+            db.operation = "DELETE",
+            db.statement = Self::QUERY,
+            db.rows_affected = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let result = tracing::Instrument::instrument(
+            async { client.execute(Self::QUERY, &[&self.id]).await },
+            span.clone(),
+        )
+        .await;
+        match &result {
+            Ok(value) => {
+                span.record("db.rows_affected", *value);
+            }
+            Err(err) => {
+                span.record("error", tracing::field::display(err));
+            }
+        }
+        result
     }
 }
 #[derive(Debug)]
@@ -281,7 +577,7 @@ pub const GET_AUTHOR_BY_ID_AND_AGE: &str = r#"-- name: GetAuthorByIdAndAge :one
 SELECT id, name, bio, age, is_active FROM authors
 WHERE id = $1 AND (age = $2 OR $2 IS NULL)
 LIMIT 1"#;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GetAuthorByIdAndAgeRow {
     pub id: i64,
     pub name: String,
@@ -300,6 +596,28 @@ impl GetAuthorByIdAndAgeRow {
         })
     }
 }
+impl FromPgRow for GetAuthorByIdAndAgeRow {
+    fn from_pg_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Self::from_row(row)
+    }
+}
+impl TryFrom<&tokio_postgres::Row> for GetAuthorByIdAndAgeRow {
+    type Error = tokio_postgres::Error;
+    fn try_from(row: &tokio_postgres::Row) -> Result<Self, Self::Error> {
+        Self::from_row(row)
+    }
+}
+/// Test query for nullable Copy type (age) with non-nullable Copy type (id)
+///
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, name, bio, age, is_active FROM authors
+/// WHERE id = $1 AND (age = $2 OR $2 IS NULL)
+/// LIMIT 1
+/// ```
+///
+/// </details>
 pub async fn get_author_by_id_and_age(
     client: &impl tokio_postgres::GenericClient,
     id: i64,
@@ -324,22 +642,68 @@ impl GetAuthorByIdAndAge {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<GetAuthorByIdAndAgeRow, tokio_postgres::Error> {
-        let row = client
-            .query_one(Self::QUERY, &[&self.id, &self.age])
-            .await?;
-        GetAuthorByIdAndAgeRow::from_row(&row)
+        let span = tracing::info_span!(
+            "GetAuthorByIdAndAge",
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            db.statement = Self::QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let result = tracing::Instrument::instrument(
+            async {
+                let row = client
+                    .query_one(Self::QUERY, &[&self.id, &self.age])
+                    .await?;
+                GetAuthorByIdAndAgeRow::from_row(&row)
+            },
+            span.clone(),
+        )
+        .await;
+        match &result {
+            Ok(_value) => {
+                span.record("db.response.returned_rows", 1u64);
+            }
+            Err(err) => {
+                span.record("error", tracing::field::display(err));
+            }
+        }
+        result
     }
     pub async fn query_opt(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Option<GetAuthorByIdAndAgeRow>, tokio_postgres::Error> {
-        let row = client
-            .query_opt(Self::QUERY, &[&self.id, &self.age])
-            .await?;
-        match row {
-            Some(ref row) => Ok(Some(GetAuthorByIdAndAgeRow::from_row(row)?)),
-            None => Ok(None),
+        let span = tracing::info_span!(
+            "GetAuthorByIdAndAge",
+            db.system = "postgresql",
+            db.operation = "SELECT",
+            db.statement = Self::QUERY,
+            db.response.returned_rows = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let result = tracing::Instrument::instrument(
+            async {
+                let row = client
+                    .query_opt(Self::QUERY, &[&self.id, &self.age])
+                    .await?;
+                match row {
+                    Some(row) => Ok(Some(GetAuthorByIdAndAgeRow::from_row(&row)?)),
+                    None => Ok(None),
+                }
+            },
+            span.clone(),
+        )
+        .await;
+        match &result {
+            Ok(value) => {
+                span.record("db.response.returned_rows", u64::from(value.is_some()));
+            }
+            Err(err) => {
+                span.record("error", tracing::field::display(err));
+            }
         }
+        result
     }
 }
 #[derive(Debug)]
@@ -383,15 +747,49 @@ pub const UPDATE_AUTHOR_STATUS: &str = r#"-- name: UpdateAuthorStatus :exec
 UPDATE authors
 SET is_active = $1, age = $2
 WHERE id = $3"#;
+/// Test query with nullable Copy type (age) and non-nullable Copy type (is_active)
+///
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// UPDATE authors
+/// SET is_active = $1, age = $2
+/// WHERE id = $3
+/// ```
+///
+/// </details>
 pub async fn update_author_status(
     client: &impl tokio_postgres::GenericClient,
     is_active: Option<bool>,
     age: Option<i32>,
     id: i64,
 ) -> Result<u64, tokio_postgres::Error> {
-    client
-        .execute(UPDATE_AUTHOR_STATUS, &[&is_active, &age, &id])
-        .await
+    let span = tracing::info_span!(
+        "UpdateAuthorStatus",
+        db.system = "postgresql",
+        db.operation = "UPDATE",
+        db.statement = UPDATE_AUTHOR_STATUS,
+        db.rows_affected = tracing::field::Empty,
+        error = tracing::field::Empty,
+    );
+    let result = tracing::Instrument::instrument(
+        async {
+            client
+                .execute(UPDATE_AUTHOR_STATUS, &[&is_active, &age, &id])
+                .await
+        },
+        span.clone(),
+    )
+    .await;
+    match &result {
+        Ok(value) => {
+            span.record("db.rows_affected", *value);
+        }
+        Err(err) => {
+            span.record("error", tracing::field::display(err));
+        }
+    }
+    result
 }
 #[derive(Debug)]
 pub struct UpdateAuthorStatus {
@@ -410,9 +808,32 @@ impl UpdateAuthorStatus {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<u64, tokio_postgres::Error> {
-        client
-            .execute(Self::QUERY, &[&self.is_active, &self.age, &self.id])
-            .await
+        let span = tracing::info_span!(
+            "UpdateAuthorStatus",
+            db.system = "postgresql",
+            db.operation = "UPDATE",
+            db.statement = Self::QUERY,
+            db.rows_affected = tracing::field::Empty,
+            error = tracing::field::Empty,
+        );
+        let result = tracing::Instrument::instrument(
+            async {
+                client
+                    .execute(Self::QUERY, &[&self.is_active, &self.age, &self.id])
+                    .await
+            },
+            span.clone(),
+        )
+        .await;
+        match &result {
+            Ok(value) => {
+                span.record("db.rows_affected", *value);
+            }
+            Err(err) => {
+                span.record("error", tracing::field::display(err));
+            }
+        }
+        result
     }
 }
 #[derive(Debug)]
@@ -464,3 +885,294 @@ impl UpdateAuthorStatusBuilder<(Option<bool>, Option<i32>, i64)> {
         UpdateAuthorStatus { is_active, age, id }
     }
 }
+pub trait Querier {
+    type Error;
+    fn get_author(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<Option<GetAuthorRow>, Self::Error>> + Send;
+    fn list_authors(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<ListAuthorsRow>, Self::Error>> + Send;
+    fn create_author(
+        &self,
+        name: &str,
+        bio: Option<&str>,
+    ) -> impl std::future::Future<Output = Result<Option<CreateAuthorRow>, Self::Error>> + Send;
+    fn delete_author(
+        &self,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<u64, Self::Error>> + Send;
+    fn get_author_by_id_and_age(
+        &self,
+        id: i64,
+        age: Option<i32>,
+    ) -> impl std::future::Future<Output = Result<Option<GetAuthorByIdAndAgeRow>, Self::Error>> + Send;
+    fn update_author_status(
+        &self,
+        is_active: Option<bool>,
+        age: Option<i32>,
+        id: i64,
+    ) -> impl std::future::Future<Output = Result<u64, Self::Error>> + Send;
+}
+#[allow(deprecated)]
+impl<C: tokio_postgres::GenericClient + Sync> Querier for C {
+    type Error = tokio_postgres::Error;
+    async fn get_author(&self, id: i64) -> Result<Option<GetAuthorRow>, Self::Error> {
+        get_author(self, id).await
+    }
+    async fn list_authors(&self) -> Result<Vec<ListAuthorsRow>, Self::Error> {
+        list_authors(self).await?.collect()
+    }
+    async fn create_author(
+        &self,
+        name: &str,
+        bio: Option<&str>,
+    ) -> Result<Option<CreateAuthorRow>, Self::Error> {
+        create_author(self, name, bio).await
+    }
+    async fn delete_author(&self, id: i64) -> Result<u64, Self::Error> {
+        delete_author(self, id).await
+    }
+    async fn get_author_by_id_and_age(
+        &self,
+        id: i64,
+        age: Option<i32>,
+    ) -> Result<Option<GetAuthorByIdAndAgeRow>, Self::Error> {
+        get_author_by_id_and_age(self, id, age).await
+    }
+    async fn update_author_status(
+        &self,
+        is_active: Option<bool>,
+        age: Option<i32>,
+        id: i64,
+    ) -> Result<u64, Self::Error> {
+        update_author_status(self, is_active, age, id).await
+    }
+}
+#[cfg(test)]
+#[derive(Debug)]
+pub enum MockQuerierCall {
+    GetAuthor(GetAuthor),
+    ListAuthors,
+    CreateAuthor(CreateAuthor<'static>),
+    DeleteAuthor(DeleteAuthor),
+    GetAuthorByIdAndAge(GetAuthorByIdAndAge),
+    UpdateAuthorStatus(UpdateAuthorStatus),
+}
+#[cfg(test)]
+impl Clone for MockQuerierCall {
+    fn clone(&self) -> Self {
+        match self {
+            Self::GetAuthor(call) => Self::GetAuthor(GetAuthor {
+                id: call.id.clone(),
+            }),
+            Self::ListAuthors => Self::ListAuthors,
+            Self::CreateAuthor(call) => Self::CreateAuthor(CreateAuthor {
+                name: call.name.clone(),
+                bio: call.bio.clone(),
+            }),
+            Self::DeleteAuthor(call) => Self::DeleteAuthor(DeleteAuthor {
+                id: call.id.clone(),
+            }),
+            Self::GetAuthorByIdAndAge(call) => Self::GetAuthorByIdAndAge(GetAuthorByIdAndAge {
+                id: call.id.clone(),
+                age: call.age.clone(),
+            }),
+            Self::UpdateAuthorStatus(call) => Self::UpdateAuthorStatus(UpdateAuthorStatus {
+                is_active: call.is_active.clone(),
+                age: call.age.clone(),
+                id: call.id.clone(),
+            }),
+        }
+    }
+}
+#[cfg(test)]
+impl PartialEq for MockQuerierCall {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::GetAuthor(call), Self::GetAuthor(other)) => call.id == other.id,
+            (Self::ListAuthors, Self::ListAuthors) => true,
+            (Self::CreateAuthor(call), Self::CreateAuthor(other)) => {
+                call.name == other.name && call.bio == other.bio
+            }
+            (Self::DeleteAuthor(call), Self::DeleteAuthor(other)) => call.id == other.id,
+            (Self::GetAuthorByIdAndAge(call), Self::GetAuthorByIdAndAge(other)) => {
+                call.id == other.id && call.age == other.age
+            }
+            (Self::UpdateAuthorStatus(call), Self::UpdateAuthorStatus(other)) => {
+                call.is_active == other.is_active && call.age == other.age && call.id == other.id
+            }
+            _ => false,
+        }
+    }
+}
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockQuerierError(pub String);
+#[cfg(test)]
+impl std::fmt::Display for MockQuerierError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+#[cfg(test)]
+impl std::error::Error for MockQuerierError {}
+#[cfg(test)]
+#[derive(Debug, Default)]
+pub struct MockQuerier {
+    calls: std::sync::Mutex<Vec<MockQuerierCall>>,
+    get_author: std::sync::Mutex<
+        std::collections::VecDeque<Result<Option<GetAuthorRow>, MockQuerierError>>,
+    >,
+    list_authors:
+        std::sync::Mutex<std::collections::VecDeque<Result<Vec<ListAuthorsRow>, MockQuerierError>>>,
+    create_author: std::sync::Mutex<
+        std::collections::VecDeque<Result<Option<CreateAuthorRow>, MockQuerierError>>,
+    >,
+    delete_author: std::sync::Mutex<std::collections::VecDeque<Result<u64, MockQuerierError>>>,
+    get_author_by_id_and_age: std::sync::Mutex<
+        std::collections::VecDeque<Result<Option<GetAuthorByIdAndAgeRow>, MockQuerierError>>,
+    >,
+    update_author_status:
+        std::sync::Mutex<std::collections::VecDeque<Result<u64, MockQuerierError>>>,
+}
+#[cfg(test)]
+impl MockQuerier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn push_get_author(&self, result: Result<Option<GetAuthorRow>, MockQuerierError>) -> &Self {
+        self.get_author.lock().unwrap().push_back(result);
+        self
+    }
+    pub fn push_list_authors(
+        &self,
+        result: Result<Vec<ListAuthorsRow>, MockQuerierError>,
+    ) -> &Self {
+        self.list_authors.lock().unwrap().push_back(result);
+        self
+    }
+    pub fn push_create_author(
+        &self,
+        result: Result<Option<CreateAuthorRow>, MockQuerierError>,
+    ) -> &Self {
+        self.create_author.lock().unwrap().push_back(result);
+        self
+    }
+    pub fn push_delete_author(&self, result: Result<u64, MockQuerierError>) -> &Self {
+        self.delete_author.lock().unwrap().push_back(result);
+        self
+    }
+    pub fn push_get_author_by_id_and_age(
+        &self,
+        result: Result<Option<GetAuthorByIdAndAgeRow>, MockQuerierError>,
+    ) -> &Self {
+        self.get_author_by_id_and_age
+            .lock()
+            .unwrap()
+            .push_back(result);
+        self
+    }
+    pub fn push_update_author_status(&self, result: Result<u64, MockQuerierError>) -> &Self {
+        self.update_author_status.lock().unwrap().push_back(result);
+        self
+    }
+    /// Returns the recorded calls in order and clears the log
+    pub fn take_calls(&self) -> Vec<MockQuerierCall> {
+        std::mem::take(&mut *self.calls.lock().unwrap())
+    }
+}
+#[cfg(test)]
+impl Querier for MockQuerier {
+    type Error = MockQuerierError;
+    async fn get_author(&self, id: i64) -> Result<Option<GetAuthorRow>, Self::Error> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(MockQuerierCall::GetAuthor(GetAuthor { id: id }));
+        self.get_author
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("MockQuerier: no queued result for `get_author`")
+    }
+    async fn list_authors(&self) -> Result<Vec<ListAuthorsRow>, Self::Error> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(MockQuerierCall::ListAuthors);
+        self.list_authors
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("MockQuerier: no queued result for `list_authors`")
+    }
+    async fn create_author(
+        &self,
+        name: &str,
+        bio: Option<&str>,
+    ) -> Result<Option<CreateAuthorRow>, Self::Error> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(MockQuerierCall::CreateAuthor(CreateAuthor {
+                name: std::borrow::Cow::Owned(name.to_owned()),
+                bio: bio.map(|v| std::borrow::Cow::Owned(v.to_owned())),
+            }));
+        self.create_author
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("MockQuerier: no queued result for `create_author`")
+    }
+    async fn delete_author(&self, id: i64) -> Result<u64, Self::Error> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(MockQuerierCall::DeleteAuthor(DeleteAuthor { id: id }));
+        self.delete_author
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("MockQuerier: no queued result for `delete_author`")
+    }
+    async fn get_author_by_id_and_age(
+        &self,
+        id: i64,
+        age: Option<i32>,
+    ) -> Result<Option<GetAuthorByIdAndAgeRow>, Self::Error> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(MockQuerierCall::GetAuthorByIdAndAge(GetAuthorByIdAndAge {
+                id: id,
+                age: age,
+            }));
+        self.get_author_by_id_and_age
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("MockQuerier: no queued result for `get_author_by_id_and_age`")
+    }
+    async fn update_author_status(
+        &self,
+        is_active: Option<bool>,
+        age: Option<i32>,
+        id: i64,
+    ) -> Result<u64, Self::Error> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(MockQuerierCall::UpdateAuthorStatus(UpdateAuthorStatus {
+                is_active: is_active,
+                age: age,
+                id: id,
+            }));
+        self.update_author_status
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("MockQuerier: no queued result for `update_author_status`")
+    }
+}
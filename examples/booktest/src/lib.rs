@@ -33,4 +33,200 @@ mod tests {
             .unwrap();
         assert_eq!(hello.say_hello.unwrap(), "hello world")
     }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn prepare_typed_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+
+        let author = queries::create_author(&ctx.client, "Alice")
+            .await
+            .unwrap()
+            .unwrap();
+        let book = queries::create_book(
+            &ctx.client,
+            author.author_id,
+            "978-4-00-000000-0",
+            queries::BookType::Fiction,
+            "Typed",
+            2024,
+            &std::time::SystemTime::now(),
+            &["typed".to_string()],
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let books = queries::books_by_tags(&ctx.client, &["typed".to_string()])
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].book_id, book.book_id);
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn query_error_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+
+        let author = queries::create_author(&ctx.client, "Carol")
+            .await
+            .unwrap()
+            .unwrap();
+        let create_book = |author_id: i32, isbn: &'static str| {
+            queries::create_book(
+                &ctx.client,
+                author_id,
+                isbn,
+                queries::BookType::NonFiction,
+                "Errors",
+                2024,
+                &std::time::SystemTime::UNIX_EPOCH,
+                &[],
+            )
+        };
+        create_book(author.author_id, "978-4-00-000000-1")
+            .await
+            .unwrap();
+
+        let err = queries::QueryError::from(
+            create_book(author.author_id, "978-4-00-000000-1")
+                .await
+                .unwrap_err(),
+        );
+        assert!(matches!(
+            err,
+            queries::QueryError::UniqueViolation {
+                constraint: Some(queries::Constraint::BooksIsbnKey),
+                ..
+            }
+        ));
+        assert!(!err.is_retryable());
+
+        let err =
+            queries::QueryError::from(create_book(-1, "978-4-00-000000-2").await.unwrap_err());
+        match err {
+            queries::QueryError::ForeignKeyViolation {
+                constraint: Some(constraint),
+                ..
+            } => assert_eq!(constraint.name(), "books_author_id_fkey"),
+            err => panic!("unexpected error: {err:?}"),
+        }
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn renamed_idents_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+
+        let author = queries::create_author(&ctx.client, "Dave")
+            .await
+            .unwrap()
+            .unwrap();
+        let book = queries::create_book(
+            &ctx.client,
+            author.author_id,
+            "978-4-00-000000-3",
+            queries::BookType::NonFiction,
+            "Renames",
+            2024,
+            &std::time::SystemTime::UNIX_EPOCH,
+            &[],
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        queries::set_book_isbn(
+            &ctx.client,
+            "Renamed",
+            &[],
+            book.book_id,
+            "978-4-00-000000-4",
+        )
+        .await
+        .unwrap();
+        let book = queries::get_book(&ctx.client, book.book_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(book.isbn, "978-4-00-000000-4");
+        assert_eq!(book.in_stock, std::time::SystemTime::UNIX_EPOCH);
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn serde_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+
+        let author = queries::create_author(&ctx.client, "Erin")
+            .await
+            .unwrap()
+            .unwrap();
+        let request = serde_json::json!({
+            "authorId": author.author_id,
+            "isbn": "978-4-00-000000-5",
+            "bookType": "NONFICTION",
+            "title": "Serde",
+            "year": 2024,
+            "inStock": { "secs_since_epoch": 0, "nanos_since_epoch": 0 },
+            "tags": ["json"],
+        });
+        // Borrowed fields are deserialized as owned, so no input has to outlive the query
+        let create_book: queries::CreateBook<'static> = serde_json::from_value(request).unwrap();
+        let book = create_book.query_one(&ctx.client).await.unwrap();
+
+        let json = serde_json::to_value(&book).unwrap();
+        assert_eq!(json["bookId"], book.book_id);
+        assert_eq!(json["bookType"], "NONFICTION");
+        assert_eq!(json["tags"], serde_json::json!(["json"]));
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn owned_queries_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+
+        let queued = {
+            let name = String::from("Frank");
+            let query = queries::CreateAuthor {
+                name: name.as_str().into(),
+            };
+            query.clone().into_owned()
+        };
+        // An owned query can be moved across tasks after its inputs are gone
+        let queued = tokio::spawn(async move { queued }).await.unwrap();
+        let author = queued.query_one(&ctx.client).await.unwrap();
+        assert_eq!(author.name, "Frank");
+
+        let query = {
+            let tags = vec!["owned".to_string()];
+            queries::BooksByTags::builder()
+                .param(tags.as_slice())
+                .build_owned()
+        };
+        let books = tokio::spawn(async move { query })
+            .await
+            .unwrap()
+            .query_many(&ctx.client)
+            .await
+            .unwrap();
+        assert!(books.is_empty());
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn query_timeout_works(ctx: &mut PgTokioTestContext) {
+        queries::sleep(&ctx.client, 0.0).await.unwrap();
+
+        let started_at = std::time::Instant::now();
+        let err = queries::QueryError::from(queries::sleep(&ctx.client, 10.0).await.unwrap_err());
+        assert!(matches!(err, queries::QueryError::QueryCanceled { .. }));
+        assert!(started_at.elapsed() < std::time::Duration::from_secs(5));
+
+        // The connection stays usable after the cancellation
+        queries::sleep(&ctx.client, 0.0).await.unwrap();
+    }
 }
@@ -1,18 +1,157 @@
 //! Code generated by sqlc. SHOULD NOT EDIT.
 //! sqlc version: v1.28.0
 //! sqlc-rust-postgres version: v0.1.4
-#[derive(Debug, Clone, postgres_types::ToSql, postgres_types::FromSql)]
+#[derive(
+    Debug,
+    Clone,
+    postgres_types::ToSql,
+    postgres_types::FromSql,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 #[postgres(name = "book_type")]
 pub enum BookType {
     #[postgres(name = "FICTION")]
+    #[serde(rename = "FICTION")]
     Fiction,
     #[postgres(name = "NONFICTION")]
-    Nonfiction,
+    #[serde(rename = "NONFICTION")]
+    NonFiction,
+}
+/// Looks up a database type such as an enum or composite by its quoted name
+async fn lookup_type(
+    client: &impl tokio_postgres::GenericClient,
+    type_name: &str,
+) -> Result<tokio_postgres::types::Type, tokio_postgres::Error> {
+    let stmt = client
+        .prepare(&format!("SELECT NULL::{}", type_name))
+        .await?;
+    Ok(stmt.columns()[0].type_().clone())
+}
+/// Database constraint named in a constraint violation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Constraint {
+    BooksIsbnKey,
+    BooksAuthorIdFkey,
+    Other(String),
+}
+impl Constraint {
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "books_isbn_key" => Self::BooksIsbnKey,
+            "books_author_id_fkey" => Self::BooksAuthorIdFkey,
+            _ => Self::Other(name.to_owned()),
+        }
+    }
+    pub fn name(&self) -> &str {
+        match self {
+            Self::BooksIsbnKey => "books_isbn_key",
+            Self::BooksAuthorIdFkey => "books_author_id_fkey",
+            Self::Other(name) => name,
+        }
+    }
+}
+/// Database error classified by its SQLSTATE code
+#[derive(Debug)]
+pub enum QueryError {
+    UniqueViolation {
+        constraint: Option<Constraint>,
+        source: tokio_postgres::Error,
+    },
+    ForeignKeyViolation {
+        constraint: Option<Constraint>,
+        source: tokio_postgres::Error,
+    },
+    NotNullViolation {
+        column: Option<String>,
+        source: tokio_postgres::Error,
+    },
+    CheckViolation {
+        constraint: Option<Constraint>,
+        source: tokio_postgres::Error,
+    },
+    ExclusionViolation {
+        constraint: Option<Constraint>,
+        source: tokio_postgres::Error,
+    },
+    SerializationFailure {
+        source: tokio_postgres::Error,
+    },
+    Deadlock {
+        source: tokio_postgres::Error,
+    },
+    QueryCanceled {
+        source: tokio_postgres::Error,
+    },
+    Other(tokio_postgres::Error),
+}
+impl QueryError {
+    /// Returns the underlying driver error
+    pub fn inner(&self) -> &tokio_postgres::Error {
+        match self {
+            Self::UniqueViolation { source, .. }
+            | Self::ForeignKeyViolation { source, .. }
+            | Self::NotNullViolation { source, .. }
+            | Self::CheckViolation { source, .. }
+            | Self::ExclusionViolation { source, .. }
+            | Self::SerializationFailure { source }
+            | Self::Deadlock { source }
+            | Self::QueryCanceled { source }
+            | Self::Other(source) => source,
+        }
+    }
+    /// Whether the transaction may succeed when retried
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::SerializationFailure { .. } | Self::Deadlock { .. }
+        )
+    }
+}
+impl From<tokio_postgres::Error> for QueryError {
+    fn from(source: tokio_postgres::Error) -> Self {
+        let Some(db_error) = source.as_db_error() else {
+            return Self::Other(source);
+        };
+        let constraint = db_error.constraint().map(Constraint::from_name);
+        let code = db_error.code();
+        if *code == tokio_postgres::error::SqlState::UNIQUE_VIOLATION {
+            Self::UniqueViolation { constraint, source }
+        } else if *code == tokio_postgres::error::SqlState::FOREIGN_KEY_VIOLATION {
+            Self::ForeignKeyViolation { constraint, source }
+        } else if *code == tokio_postgres::error::SqlState::NOT_NULL_VIOLATION {
+            let column = db_error.column().map(str::to_owned);
+            Self::NotNullViolation { column, source }
+        } else if *code == tokio_postgres::error::SqlState::CHECK_VIOLATION {
+            Self::CheckViolation { constraint, source }
+        } else if *code == tokio_postgres::error::SqlState::EXCLUSION_VIOLATION {
+            Self::ExclusionViolation { constraint, source }
+        } else if *code == tokio_postgres::error::SqlState::T_R_SERIALIZATION_FAILURE {
+            Self::SerializationFailure { source }
+        } else if *code == tokio_postgres::error::SqlState::T_R_DEADLOCK_DETECTED {
+            Self::Deadlock { source }
+        } else if *code == tokio_postgres::error::SqlState::QUERY_CANCELED {
+            Self::QueryCanceled { source }
+        } else {
+            Self::Other(source)
+        }
+    }
+}
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.inner(), f)
+    }
+}
+impl std::error::Error for QueryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.inner())
+    }
 }
 pub const GET_AUTHOR: &str = r#"-- name: GetAuthor :one
 SELECT author_id, name FROM authors
 WHERE author_id = $1"#;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetAuthorRow {
     pub author_id: i32,
     pub name: String,
@@ -25,6 +164,14 @@ impl GetAuthorRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT author_id, name FROM authors
+/// WHERE author_id = $1
+/// ```
+///
+/// </details>
 pub async fn get_author(
     client: &impl tokio_postgres::GenericClient,
     author_id: i32,
@@ -34,7 +181,8 @@ pub async fn get_author(
     };
     query_struct.query_opt(client).await
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetAuthor {
     pub author_id: i32,
 }
@@ -48,17 +196,43 @@ impl GetAuthor {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<GetAuthorRow, tokio_postgres::Error> {
-        let row = client.query_one(Self::QUERY, &[&self.author_id]).await?;
-        GetAuthorRow::from_row(&row)
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::INT4])
+                .await?;
+            let row = client.query_one(&stmt, &[&self.author_id]).await?;
+            GetAuthorRow::from_row(&row)
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
     pub async fn query_opt(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Option<GetAuthorRow>, tokio_postgres::Error> {
-        let row = client.query_opt(Self::QUERY, &[&self.author_id]).await?;
-        match row {
-            Some(ref row) => Ok(Some(GetAuthorRow::from_row(row)?)),
-            None => Ok(None),
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::INT4])
+                .await?;
+            let row = client.query_opt(&stmt, &[&self.author_id]).await?;
+            match row {
+                Some(row) => Ok(Some(GetAuthorRow::from_row(&row)?)),
+                None => Ok(None),
+            }
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
         }
     }
 }
@@ -93,7 +267,8 @@ impl GetAuthorBuilder<i32> {
 pub const GET_BOOK: &str = r#"-- name: GetBook :one
 SELECT book_id, author_id, isbn, book_type, title, year, available, tags FROM books
 WHERE book_id = $1"#;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetBookRow {
     pub book_id: i32,
     pub author_id: i32,
@@ -101,7 +276,7 @@ pub struct GetBookRow {
     pub book_type: BookType,
     pub title: String,
     pub year: i32,
-    pub available: ::std::time::SystemTime,
+    pub in_stock: ::std::time::SystemTime,
     pub tags: Vec<String>,
 }
 impl GetBookRow {
@@ -113,11 +288,19 @@ impl GetBookRow {
             book_type: row.try_get(3)?,
             title: row.try_get(4)?,
             year: row.try_get(5)?,
-            available: row.try_get(6)?,
+            in_stock: row.try_get(6)?,
             tags: row.try_get(7)?,
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT book_id, author_id, isbn, book_type, title, year, available, tags FROM books
+/// WHERE book_id = $1
+/// ```
+///
+/// </details>
 pub async fn get_book(
     client: &impl tokio_postgres::GenericClient,
     book_id: i32,
@@ -125,7 +308,8 @@ pub async fn get_book(
     let query_struct = GetBook { book_id: book_id };
     query_struct.query_opt(client).await
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct GetBook {
     pub book_id: i32,
 }
@@ -139,17 +323,43 @@ impl GetBook {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<GetBookRow, tokio_postgres::Error> {
-        let row = client.query_one(Self::QUERY, &[&self.book_id]).await?;
-        GetBookRow::from_row(&row)
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::INT4])
+                .await?;
+            let row = client.query_one(&stmt, &[&self.book_id]).await?;
+            GetBookRow::from_row(&row)
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
     pub async fn query_opt(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Option<GetBookRow>, tokio_postgres::Error> {
-        let row = client.query_opt(Self::QUERY, &[&self.book_id]).await?;
-        match row {
-            Some(ref row) => Ok(Some(GetBookRow::from_row(row)?)),
-            None => Ok(None),
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::INT4])
+                .await?;
+            let row = client.query_opt(&stmt, &[&self.book_id]).await?;
+            match row {
+                Some(row) => Ok(Some(GetBookRow::from_row(&row)?)),
+                None => Ok(None),
+            }
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
         }
     }
 }
@@ -184,13 +394,35 @@ impl GetBookBuilder<i32> {
 pub const DELETE_BOOK: &str = r#"-- name: DeleteBook :exec
 DELETE FROM books
 WHERE book_id = $1"#;
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// DELETE FROM books
+/// WHERE book_id = $1
+/// ```
+///
+/// </details>
 pub async fn delete_book(
     client: &impl tokio_postgres::GenericClient,
     book_id: i32,
 ) -> Result<u64, tokio_postgres::Error> {
-    client.execute(DELETE_BOOK, &[&book_id]).await
+    let cancel_token = client.client().cancel_token();
+    let mut query = std::pin::pin!(async {
+        let stmt = client
+            .prepare_typed(DELETE_BOOK, &[tokio_postgres::types::Type::INT4])
+            .await?;
+        client.execute(&stmt, &[&book_id]).await
+    });
+    match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+        Ok(result) => result,
+        Err(_) => {
+            cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+            query.await
+        }
+    }
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct DeleteBook {
     pub book_id: i32,
 }
@@ -204,7 +436,20 @@ impl DeleteBook {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<u64, tokio_postgres::Error> {
-        client.execute(Self::QUERY, &[&self.book_id]).await
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::INT4])
+                .await?;
+            client.execute(&stmt, &[&self.book_id]).await
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
 }
 #[derive(Debug)]
@@ -238,7 +483,8 @@ impl DeleteBookBuilder<i32> {
 pub const BOOKS_BY_TITLE_YEAR: &str = r#"-- name: BooksByTitleYear :many
 SELECT book_id, author_id, isbn, book_type, title, year, available, tags FROM books
 WHERE title = $1 AND year = $2"#;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BooksByTitleYearRow {
     pub book_id: i32,
     pub author_id: i32,
@@ -246,7 +492,7 @@ pub struct BooksByTitleYearRow {
     pub book_type: BookType,
     pub title: String,
     pub year: i32,
-    pub available: ::std::time::SystemTime,
+    pub in_stock: ::std::time::SystemTime,
     pub tags: Vec<String>,
 }
 impl BooksByTitleYearRow {
@@ -258,11 +504,19 @@ impl BooksByTitleYearRow {
             book_type: row.try_get(3)?,
             title: row.try_get(4)?,
             year: row.try_get(5)?,
-            available: row.try_get(6)?,
+            in_stock: row.try_get(6)?,
             tags: row.try_get(7)?,
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT book_id, author_id, isbn, book_type, title, year, available, tags FROM books
+/// WHERE title = $1 AND year = $2
+/// ```
+///
+/// </details>
 pub async fn books_by_title_year(
     client: &impl tokio_postgres::GenericClient,
     title: &str,
@@ -271,10 +525,30 @@ pub async fn books_by_title_year(
     impl Iterator<Item = Result<BooksByTitleYearRow, tokio_postgres::Error>>,
     tokio_postgres::Error,
 > {
-    let rows = client.query(BOOKS_BY_TITLE_YEAR, &[&title, &year]).await?;
-    Ok(rows.into_iter().map(|r| BooksByTitleYearRow::from_row(&r)))
+    let cancel_token = client.client().cancel_token();
+    let mut query = std::pin::pin!(async {
+        let stmt = client
+            .prepare_typed(
+                BOOKS_BY_TITLE_YEAR,
+                &[
+                    tokio_postgres::types::Type::TEXT,
+                    tokio_postgres::types::Type::INT4,
+                ],
+            )
+            .await?;
+        let rows = client.query(&stmt, &[&title, &year]).await?;
+        Ok(rows.into_iter().map(|r| BooksByTitleYearRow::from_row(&r)))
+    });
+    match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+        Ok(result) => result,
+        Err(_) => {
+            cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+            query.await
+        }
+    }
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BooksByTitleYear<'a> {
     pub title: std::borrow::Cow<'a, str>,
     pub year: i32,
@@ -283,18 +557,44 @@ impl<'a> BooksByTitleYear<'a> {
     pub const QUERY: &'static str = r#"-- name: BooksByTitleYear :many
 SELECT book_id, author_id, isbn, book_type, title, year, available, tags FROM books
 WHERE title = $1 AND year = $2"#;
+    /// Converts into a query owning all its parameters, e.g. to move it into a spawned task
+    pub fn into_owned(self) -> BooksByTitleYear<'static> {
+        BooksByTitleYear {
+            title: std::borrow::Cow::Owned(self.title.into_owned()),
+            year: self.year,
+        }
+    }
 }
 impl<'a> BooksByTitleYear<'a> {
     pub async fn query_many(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Vec<BooksByTitleYearRow>, tokio_postgres::Error> {
-        let rows = client
-            .query(Self::QUERY, &[&self.title.as_ref(), &self.year])
-            .await?;
-        rows.into_iter()
-            .map(|r| BooksByTitleYearRow::from_row(&r))
-            .collect()
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(
+                    Self::QUERY,
+                    &[
+                        tokio_postgres::types::Type::TEXT,
+                        tokio_postgres::types::Type::INT4,
+                    ],
+                )
+                .await?;
+            let rows = client
+                .query(&stmt, &[&self.title.as_ref(), &self.year])
+                .await?;
+            rows.into_iter()
+                .map(|r| BooksByTitleYearRow::from_row(&r))
+                .collect::<Result<Vec<_>, _>>()
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
     pub async fn query_raw(
         &self,
@@ -303,10 +603,29 @@ impl<'a> BooksByTitleYear<'a> {
         impl Iterator<Item = Result<BooksByTitleYearRow, tokio_postgres::Error>>,
         tokio_postgres::Error,
     > {
-        let rows = client
-            .query(Self::QUERY, &[&self.title.as_ref(), &self.year])
-            .await?;
-        Ok(rows.into_iter().map(|r| BooksByTitleYearRow::from_row(&r)))
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(
+                    Self::QUERY,
+                    &[
+                        tokio_postgres::types::Type::TEXT,
+                        tokio_postgres::types::Type::INT4,
+                    ],
+                )
+                .await?;
+            let rows = client
+                .query(&stmt, &[&self.title.as_ref(), &self.year])
+                .await?;
+            Ok(rows.into_iter().map(|r| BooksByTitleYearRow::from_row(&r)))
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
 }
 #[derive(Debug, Default)]
@@ -337,6 +656,9 @@ impl<'a> BooksByTitleYearBuilder<'a> {
             year: self.year.expect("Missing required field"),
         }
     }
+    pub fn build_owned(self) -> BooksByTitleYear<'static> {
+        self.build().into_owned()
+    }
 }
 pub const BOOKS_BY_TAGS: &str = r#"-- name: BooksByTags :many
 SELECT 
@@ -348,7 +670,8 @@ SELECT
 FROM books
 LEFT JOIN authors ON books.author_id = authors.author_id
 WHERE tags && $1::varchar[]"#;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BooksByTagsRow {
     pub book_id: i32,
     pub title: String,
@@ -367,6 +690,21 @@ impl BooksByTagsRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT
+///  book_id,
+///  title,
+///  name,
+///  isbn,
+///  tags
+/// FROM books
+/// LEFT JOIN authors ON books.author_id = authors.author_id
+/// WHERE tags && $1::varchar[]
+/// ```
+///
+/// </details>
 pub async fn books_by_tags(
     client: &impl tokio_postgres::GenericClient,
     param: &[String],
@@ -374,10 +712,24 @@ pub async fn books_by_tags(
     impl Iterator<Item = Result<BooksByTagsRow, tokio_postgres::Error>>,
     tokio_postgres::Error,
 > {
-    let rows = client.query(BOOKS_BY_TAGS, &[&param]).await?;
-    Ok(rows.into_iter().map(|r| BooksByTagsRow::from_row(&r)))
+    let cancel_token = client.client().cancel_token();
+    let mut query = std::pin::pin!(async {
+        let stmt = client
+            .prepare_typed(BOOKS_BY_TAGS, &[tokio_postgres::types::Type::VARCHAR_ARRAY])
+            .await?;
+        let rows = client.query(&stmt, &[&param]).await?;
+        Ok(rows.into_iter().map(|r| BooksByTagsRow::from_row(&r)))
+    });
+    match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+        Ok(result) => result,
+        Err(_) => {
+            cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+            query.await
+        }
+    }
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct BooksByTags<'a> {
     pub param: std::borrow::Cow<'a, [String]>,
 }
@@ -392,16 +744,35 @@ SELECT
 FROM books
 LEFT JOIN authors ON books.author_id = authors.author_id
 WHERE tags && $1::varchar[]"#;
+    /// Converts into a query owning all its parameters, e.g. to move it into a spawned task
+    pub fn into_owned(self) -> BooksByTags<'static> {
+        BooksByTags {
+            param: std::borrow::Cow::Owned(self.param.into_owned()),
+        }
+    }
 }
 impl<'a> BooksByTags<'a> {
     pub async fn query_many(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Vec<BooksByTagsRow>, tokio_postgres::Error> {
-        let rows = client.query(Self::QUERY, &[&self.param.as_ref()]).await?;
-        rows.into_iter()
-            .map(|r| BooksByTagsRow::from_row(&r))
-            .collect()
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::VARCHAR_ARRAY])
+                .await?;
+            let rows = client.query(&stmt, &[&self.param.as_ref()]).await?;
+            rows.into_iter()
+                .map(|r| BooksByTagsRow::from_row(&r))
+                .collect::<Result<Vec<_>, _>>()
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
     pub async fn query_raw(
         &self,
@@ -410,8 +781,21 @@ impl<'a> BooksByTags<'a> {
         impl Iterator<Item = Result<BooksByTagsRow, tokio_postgres::Error>>,
         tokio_postgres::Error,
     > {
-        let rows = client.query(Self::QUERY, &[&self.param.as_ref()]).await?;
-        Ok(rows.into_iter().map(|r| BooksByTagsRow::from_row(&r)))
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::VARCHAR_ARRAY])
+                .await?;
+            let rows = client.query(&stmt, &[&self.param.as_ref()]).await?;
+            Ok(rows.into_iter().map(|r| BooksByTagsRow::from_row(&r)))
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
 }
 #[derive(Debug, Default)]
@@ -436,11 +820,15 @@ impl<'a> BooksByTagsBuilder<'a> {
             param: self.param.expect("Missing required field"),
         }
     }
+    pub fn build_owned(self) -> BooksByTags<'static> {
+        self.build().into_owned()
+    }
 }
 pub const CREATE_AUTHOR: &str = r#"-- name: CreateAuthor :one
 INSERT INTO authors (name) VALUES ($1)
 RETURNING author_id, name"#;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateAuthorRow {
     pub author_id: i32,
     pub name: String,
@@ -453,6 +841,14 @@ impl CreateAuthorRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// INSERT INTO authors (name) VALUES ($1)
+/// RETURNING author_id, name
+/// ```
+///
+/// </details>
 pub async fn create_author(
     client: &impl tokio_postgres::GenericClient,
     name: &str,
@@ -462,7 +858,8 @@ pub async fn create_author(
     };
     query_struct.query_opt(client).await
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateAuthor<'a> {
     pub name: std::borrow::Cow<'a, str>,
 }
@@ -470,27 +867,55 @@ impl<'a> CreateAuthor<'a> {
     pub const QUERY: &'static str = r#"-- name: CreateAuthor :one
 INSERT INTO authors (name) VALUES ($1)
 RETURNING author_id, name"#;
+    /// Converts into a query owning all its parameters, e.g. to move it into a spawned task
+    pub fn into_owned(self) -> CreateAuthor<'static> {
+        CreateAuthor {
+            name: std::borrow::Cow::Owned(self.name.into_owned()),
+        }
+    }
 }
 impl<'a> CreateAuthor<'a> {
     pub async fn query_one(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<CreateAuthorRow, tokio_postgres::Error> {
-        let row = client
-            .query_one(Self::QUERY, &[&self.name.as_ref()])
-            .await?;
-        CreateAuthorRow::from_row(&row)
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::TEXT])
+                .await?;
+            let row = client.query_one(&stmt, &[&self.name.as_ref()]).await?;
+            CreateAuthorRow::from_row(&row)
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
     pub async fn query_opt(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Option<CreateAuthorRow>, tokio_postgres::Error> {
-        let row = client
-            .query_opt(Self::QUERY, &[&self.name.as_ref()])
-            .await?;
-        match row {
-            Some(ref row) => Ok(Some(CreateAuthorRow::from_row(row)?)),
-            None => Ok(None),
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::TEXT])
+                .await?;
+            let row = client.query_opt(&stmt, &[&self.name.as_ref()]).await?;
+            match row {
+                Some(row) => Ok(Some(CreateAuthorRow::from_row(&row)?)),
+                None => Ok(None),
+            }
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
         }
     }
 }
@@ -516,6 +941,9 @@ impl<'a> CreateAuthorBuilder<'a> {
             name: self.name.expect("Missing required field"),
         }
     }
+    pub fn build_owned(self) -> CreateAuthor<'static> {
+        self.build().into_owned()
+    }
 }
 pub const CREATE_BOOK: &str = r#"-- name: CreateBook :one
 INSERT INTO books (
@@ -536,7 +964,8 @@ INSERT INTO books (
     $7
 )
 RETURNING book_id, author_id, isbn, book_type, title, year, available, tags"#;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateBookRow {
     pub book_id: i32,
     pub author_id: i32,
@@ -544,7 +973,7 @@ pub struct CreateBookRow {
     pub book_type: BookType,
     pub title: String,
     pub year: i32,
-    pub available: ::std::time::SystemTime,
+    pub in_stock: ::std::time::SystemTime,
     pub tags: Vec<String>,
 }
 impl CreateBookRow {
@@ -556,11 +985,35 @@ impl CreateBookRow {
             book_type: row.try_get(3)?,
             title: row.try_get(4)?,
             year: row.try_get(5)?,
-            available: row.try_get(6)?,
+            in_stock: row.try_get(6)?,
             tags: row.try_get(7)?,
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// INSERT INTO books (
+///    author_id,
+///    isbn,
+///    book_type,
+///    title,
+///    year,
+///    available,
+///    tags
+/// ) VALUES (
+///    $1,
+///    $2,
+///    $3,
+///    $4,
+///    $5,
+///    $6,
+///    $7
+/// )
+/// RETURNING book_id, author_id, isbn, book_type, title, year, available, tags
+/// ```
+///
+/// </details>
 pub async fn create_book(
     client: &impl tokio_postgres::GenericClient,
     author_id: i32,
@@ -568,7 +1021,7 @@ pub async fn create_book(
     book_type: BookType,
     title: &str,
     year: i32,
-    available: &::std::time::SystemTime,
+    in_stock: &::std::time::SystemTime,
     tags: &[String],
 ) -> Result<Option<CreateBookRow>, tokio_postgres::Error> {
     let query_struct = CreateBook {
@@ -577,19 +1030,20 @@ pub async fn create_book(
         book_type: book_type,
         title: std::borrow::Cow::Borrowed(title),
         year: year,
-        available: std::borrow::Cow::Borrowed(available),
+        in_stock: std::borrow::Cow::Borrowed(in_stock),
         tags: std::borrow::Cow::Borrowed(tags),
     };
     query_struct.query_opt(client).await
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CreateBook<'a> {
     pub author_id: i32,
     pub isbn: std::borrow::Cow<'a, str>,
     pub book_type: BookType,
     pub title: std::borrow::Cow<'a, str>,
     pub year: i32,
-    pub available: std::borrow::Cow<'a, ::std::time::SystemTime>,
+    pub in_stock: std::borrow::Cow<'a, ::std::time::SystemTime>,
     pub tags: std::borrow::Cow<'a, [String]>,
 }
 impl<'a> CreateBook<'a> {
@@ -612,49 +1066,105 @@ INSERT INTO books (
     $7
 )
 RETURNING book_id, author_id, isbn, book_type, title, year, available, tags"#;
+    /// Converts into a query owning all its parameters, e.g. to move it into a spawned task
+    pub fn into_owned(self) -> CreateBook<'static> {
+        CreateBook {
+            author_id: self.author_id,
+            isbn: std::borrow::Cow::Owned(self.isbn.into_owned()),
+            book_type: self.book_type,
+            title: std::borrow::Cow::Owned(self.title.into_owned()),
+            year: self.year,
+            in_stock: std::borrow::Cow::Owned(self.in_stock.into_owned()),
+            tags: std::borrow::Cow::Owned(self.tags.into_owned()),
+        }
+    }
 }
 impl<'a> CreateBook<'a> {
     pub async fn query_one(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<CreateBookRow, tokio_postgres::Error> {
-        let row = client
-            .query_one(
-                Self::QUERY,
-                &[
-                    &self.author_id,
-                    &self.isbn.as_ref(),
-                    &self.book_type,
-                    &self.title.as_ref(),
-                    &self.year,
-                    &self.available.as_ref(),
-                    &self.tags.as_ref(),
-                ],
-            )
-            .await?;
-        CreateBookRow::from_row(&row)
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = {
+                let param_types = [
+                    tokio_postgres::types::Type::INT4,
+                    tokio_postgres::types::Type::TEXT,
+                    lookup_type(client, "\"book_type\"").await?,
+                    tokio_postgres::types::Type::TEXT,
+                    tokio_postgres::types::Type::INT4,
+                    tokio_postgres::types::Type::TIMESTAMPTZ,
+                    tokio_postgres::types::Type::VARCHAR_ARRAY,
+                ];
+                client.prepare_typed(Self::QUERY, &param_types).await?
+            };
+            let row = client
+                .query_one(
+                    &stmt,
+                    &[
+                        &self.author_id,
+                        &self.isbn.as_ref(),
+                        &self.book_type,
+                        &self.title.as_ref(),
+                        &self.year,
+                        &self.in_stock.as_ref(),
+                        &self.tags.as_ref(),
+                    ],
+                )
+                .await?;
+            CreateBookRow::from_row(&row)
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
     pub async fn query_opt(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Option<CreateBookRow>, tokio_postgres::Error> {
-        let row = client
-            .query_opt(
-                Self::QUERY,
-                &[
-                    &self.author_id,
-                    &self.isbn.as_ref(),
-                    &self.book_type,
-                    &self.title.as_ref(),
-                    &self.year,
-                    &self.available.as_ref(),
-                    &self.tags.as_ref(),
-                ],
-            )
-            .await?;
-        match row {
-            Some(ref row) => Ok(Some(CreateBookRow::from_row(row)?)),
-            None => Ok(None),
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = {
+                let param_types = [
+                    tokio_postgres::types::Type::INT4,
+                    tokio_postgres::types::Type::TEXT,
+                    lookup_type(client, "\"book_type\"").await?,
+                    tokio_postgres::types::Type::TEXT,
+                    tokio_postgres::types::Type::INT4,
+                    tokio_postgres::types::Type::TIMESTAMPTZ,
+                    tokio_postgres::types::Type::VARCHAR_ARRAY,
+                ];
+                client.prepare_typed(Self::QUERY, &param_types).await?
+            };
+            let row = client
+                .query_opt(
+                    &stmt,
+                    &[
+                        &self.author_id,
+                        &self.isbn.as_ref(),
+                        &self.book_type,
+                        &self.title.as_ref(),
+                        &self.year,
+                        &self.in_stock.as_ref(),
+                        &self.tags.as_ref(),
+                    ],
+                )
+                .await?;
+            match row {
+                Some(row) => Ok(Some(CreateBookRow::from_row(&row)?)),
+                None => Ok(None),
+            }
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
         }
     }
 }
@@ -665,7 +1175,7 @@ pub struct CreateBookBuilder<'a> {
     book_type: Option<BookType>,
     title: Option<std::borrow::Cow<'a, str>>,
     year: Option<i32>,
-    available: Option<std::borrow::Cow<'a, ::std::time::SystemTime>>,
+    in_stock: Option<std::borrow::Cow<'a, ::std::time::SystemTime>>,
     tags: Option<std::borrow::Cow<'a, [String]>>,
 }
 impl<'a> CreateBook<'a> {
@@ -700,11 +1210,11 @@ impl<'a> CreateBookBuilder<'a> {
         self.year = Some(year);
         self
     }
-    pub fn available<T>(mut self, available: T) -> Self
+    pub fn in_stock<T>(mut self, in_stock: T) -> Self
     where
         T: Into<std::borrow::Cow<'a, ::std::time::SystemTime>>,
     {
-        self.available = Some(available.into());
+        self.in_stock = Some(in_stock.into());
         self
     }
     pub fn tags<T>(mut self, tags: T) -> Self
@@ -721,26 +1231,57 @@ impl<'a> CreateBookBuilder<'a> {
             book_type: self.book_type.expect("Missing required field"),
             title: self.title.expect("Missing required field"),
             year: self.year.expect("Missing required field"),
-            available: self.available.expect("Missing required field"),
+            in_stock: self.in_stock.expect("Missing required field"),
             tags: self.tags.expect("Missing required field"),
         }
     }
+    pub fn build_owned(self) -> CreateBook<'static> {
+        self.build().into_owned()
+    }
 }
 pub const UPDATE_BOOK: &str = r#"-- name: UpdateBook :exec
 UPDATE books
 SET title = $1, tags = $2
 WHERE book_id = $3"#;
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// UPDATE books
+/// SET title = $1, tags = $2
+/// WHERE book_id = $3
+/// ```
+///
+/// </details>
 pub async fn update_book(
     client: &impl tokio_postgres::GenericClient,
     title: &str,
     tags: &[String],
     book_id: i32,
 ) -> Result<u64, tokio_postgres::Error> {
-    client
-        .execute(UPDATE_BOOK, &[&title, &tags, &book_id])
-        .await
+    let cancel_token = client.client().cancel_token();
+    let mut query = std::pin::pin!(async {
+        let stmt = client
+            .prepare_typed(
+                UPDATE_BOOK,
+                &[
+                    tokio_postgres::types::Type::TEXT,
+                    tokio_postgres::types::Type::VARCHAR_ARRAY,
+                    tokio_postgres::types::Type::INT4,
+                ],
+            )
+            .await?;
+        client.execute(&stmt, &[&title, &tags, &book_id]).await
+    });
+    match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+        Ok(result) => result,
+        Err(_) => {
+            cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+            query.await
+        }
+    }
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct UpdateBook<'a> {
     pub title: std::borrow::Cow<'a, str>,
     pub tags: std::borrow::Cow<'a, [String]>,
@@ -751,18 +1292,46 @@ impl<'a> UpdateBook<'a> {
 UPDATE books
 SET title = $1, tags = $2
 WHERE book_id = $3"#;
+    /// Converts into a query owning all its parameters, e.g. to move it into a spawned task
+    pub fn into_owned(self) -> UpdateBook<'static> {
+        UpdateBook {
+            title: std::borrow::Cow::Owned(self.title.into_owned()),
+            tags: std::borrow::Cow::Owned(self.tags.into_owned()),
+            book_id: self.book_id,
+        }
+    }
 }
 impl<'a> UpdateBook<'a> {
     pub async fn execute(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<u64, tokio_postgres::Error> {
-        client
-            .execute(
-                Self::QUERY,
-                &[&self.title.as_ref(), &self.tags.as_ref(), &self.book_id],
-            )
-            .await
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(
+                    Self::QUERY,
+                    &[
+                        tokio_postgres::types::Type::TEXT,
+                        tokio_postgres::types::Type::VARCHAR_ARRAY,
+                        tokio_postgres::types::Type::INT4,
+                    ],
+                )
+                .await?;
+            client
+                .execute(
+                    &stmt,
+                    &[&self.title.as_ref(), &self.tags.as_ref(), &self.book_id],
+                )
+                .await
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
 }
 #[derive(Debug, Default)]
@@ -802,66 +1371,130 @@ impl<'a> UpdateBookBuilder<'a> {
             book_id: self.book_id.expect("Missing required field"),
         }
     }
+    pub fn build_owned(self) -> UpdateBook<'static> {
+        self.build().into_owned()
+    }
 }
-pub const UPDATE_BOOK_ISBN: &str = r#"-- name: UpdateBookISBN :exec
+pub const SET_BOOK_ISBN: &str = r#"-- name: UpdateBookISBN :exec
 UPDATE books
 SET title = $1, tags = $2, isbn = $4
 WHERE book_id = $3"#;
-pub async fn update_book_isbn(
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// UPDATE books
+/// SET title = $1, tags = $2, isbn = $4
+/// WHERE book_id = $3
+/// ```
+///
+/// </details>
+pub async fn set_book_isbn(
     client: &impl tokio_postgres::GenericClient,
     title: &str,
     tags: &[String],
     book_id: i32,
     isbn: &str,
 ) -> Result<u64, tokio_postgres::Error> {
-    client
-        .execute(UPDATE_BOOK_ISBN, &[&title, &tags, &book_id, &isbn])
-        .await
+    let cancel_token = client.client().cancel_token();
+    let mut query = std::pin::pin!(async {
+        let stmt = client
+            .prepare_typed(
+                SET_BOOK_ISBN,
+                &[
+                    tokio_postgres::types::Type::TEXT,
+                    tokio_postgres::types::Type::VARCHAR_ARRAY,
+                    tokio_postgres::types::Type::INT4,
+                    tokio_postgres::types::Type::TEXT,
+                ],
+            )
+            .await?;
+        client
+            .execute(&stmt, &[&title, &tags, &book_id, &isbn])
+            .await
+    });
+    match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+        Ok(result) => result,
+        Err(_) => {
+            cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+            query.await
+        }
+    }
 }
-#[derive(Debug)]
-pub struct UpdateBookIsbn<'a> {
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetBookIsbn<'a> {
     pub title: std::borrow::Cow<'a, str>,
     pub tags: std::borrow::Cow<'a, [String]>,
     pub book_id: i32,
     pub isbn: std::borrow::Cow<'a, str>,
 }
-impl<'a> UpdateBookIsbn<'a> {
+impl<'a> SetBookIsbn<'a> {
     pub const QUERY: &'static str = r#"-- name: UpdateBookISBN :exec
 UPDATE books
 SET title = $1, tags = $2, isbn = $4
 WHERE book_id = $3"#;
+    /// Converts into a query owning all its parameters, e.g. to move it into a spawned task
+    pub fn into_owned(self) -> SetBookIsbn<'static> {
+        SetBookIsbn {
+            title: std::borrow::Cow::Owned(self.title.into_owned()),
+            tags: std::borrow::Cow::Owned(self.tags.into_owned()),
+            book_id: self.book_id,
+            isbn: std::borrow::Cow::Owned(self.isbn.into_owned()),
+        }
+    }
 }
-impl<'a> UpdateBookIsbn<'a> {
+impl<'a> SetBookIsbn<'a> {
     pub async fn execute(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<u64, tokio_postgres::Error> {
-        client
-            .execute(
-                Self::QUERY,
-                &[
-                    &self.title.as_ref(),
-                    &self.tags.as_ref(),
-                    &self.book_id,
-                    &self.isbn.as_ref(),
-                ],
-            )
-            .await
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(
+                    Self::QUERY,
+                    &[
+                        tokio_postgres::types::Type::TEXT,
+                        tokio_postgres::types::Type::VARCHAR_ARRAY,
+                        tokio_postgres::types::Type::INT4,
+                        tokio_postgres::types::Type::TEXT,
+                    ],
+                )
+                .await?;
+            client
+                .execute(
+                    &stmt,
+                    &[
+                        &self.title.as_ref(),
+                        &self.tags.as_ref(),
+                        &self.book_id,
+                        &self.isbn.as_ref(),
+                    ],
+                )
+                .await
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
 }
 #[derive(Debug, Default)]
-pub struct UpdateBookIsbnBuilder<'a> {
+pub struct SetBookIsbnBuilder<'a> {
     title: Option<std::borrow::Cow<'a, str>>,
     tags: Option<std::borrow::Cow<'a, [String]>>,
     book_id: Option<i32>,
     isbn: Option<std::borrow::Cow<'a, str>>,
 }
-impl<'a> UpdateBookIsbn<'a> {
-    pub fn builder() -> UpdateBookIsbnBuilder<'a> {
-        UpdateBookIsbnBuilder::default()
+impl<'a> SetBookIsbn<'a> {
+    pub fn builder() -> SetBookIsbnBuilder<'a> {
+        SetBookIsbnBuilder::default()
     }
 }
-impl<'a> UpdateBookIsbnBuilder<'a> {
+impl<'a> SetBookIsbnBuilder<'a> {
     pub fn title<T>(mut self, title: T) -> Self
     where
         T: Into<std::borrow::Cow<'a, str>>,
@@ -887,18 +1520,22 @@ impl<'a> UpdateBookIsbnBuilder<'a> {
         self.isbn = Some(isbn.into());
         self
     }
-    pub fn build(self) -> UpdateBookIsbn<'a> {
-        UpdateBookIsbn {
+    pub fn build(self) -> SetBookIsbn<'a> {
+        SetBookIsbn {
             title: self.title.expect("Missing required field"),
             tags: self.tags.expect("Missing required field"),
             book_id: self.book_id.expect("Missing required field"),
             isbn: self.isbn.expect("Missing required field"),
         }
     }
+    pub fn build_owned(self) -> SetBookIsbn<'static> {
+        self.build().into_owned()
+    }
 }
 pub const SAY_HELLO: &str = r#"-- name: SayHello :one
 select say_hello from say_hello($1)"#;
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SayHelloRow {
     pub say_hello: Option<String>,
 }
@@ -909,6 +1546,13 @@ impl SayHelloRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// select say_hello from say_hello($1)
+/// ```
+///
+/// </details>
 pub async fn say_hello(
     client: &impl tokio_postgres::GenericClient,
     s: &str,
@@ -918,30 +1562,63 @@ pub async fn say_hello(
     };
     query_struct.query_opt(client).await
 }
-#[derive(Debug)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct SayHello<'a> {
     pub s: std::borrow::Cow<'a, str>,
 }
 impl<'a> SayHello<'a> {
     pub const QUERY: &'static str = r#"-- name: SayHello :one
 select say_hello from say_hello($1)"#;
+    /// Converts into a query owning all its parameters, e.g. to move it into a spawned task
+    pub fn into_owned(self) -> SayHello<'static> {
+        SayHello {
+            s: std::borrow::Cow::Owned(self.s.into_owned()),
+        }
+    }
 }
 impl<'a> SayHello<'a> {
     pub async fn query_one(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<SayHelloRow, tokio_postgres::Error> {
-        let row = client.query_one(Self::QUERY, &[&self.s.as_ref()]).await?;
-        SayHelloRow::from_row(&row)
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::TEXT])
+                .await?;
+            let row = client.query_one(&stmt, &[&self.s.as_ref()]).await?;
+            SayHelloRow::from_row(&row)
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
     }
     pub async fn query_opt(
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Option<SayHelloRow>, tokio_postgres::Error> {
-        let row = client.query_opt(Self::QUERY, &[&self.s.as_ref()]).await?;
-        match row {
-            Some(ref row) => Ok(Some(SayHelloRow::from_row(row)?)),
-            None => Ok(None),
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::TEXT])
+                .await?;
+            let row = client.query_opt(&stmt, &[&self.s.as_ref()]).await?;
+            match row {
+                Some(row) => Ok(Some(SayHelloRow::from_row(&row)?)),
+                None => Ok(None),
+            }
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(5000), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
         }
     }
 }
@@ -967,4 +1644,93 @@ impl<'a> SayHelloBuilder<'a> {
             s: self.s.expect("Missing required field"),
         }
     }
+    pub fn build_owned(self) -> SayHello<'static> {
+        self.build().into_owned()
+    }
+}
+pub const SLEEP: &str = r#"-- name: Sleep :exec
+SELECT pg_sleep($1::float8)"#;
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT pg_sleep($1::float8)
+/// ```
+///
+/// </details>
+pub async fn sleep(
+    client: &impl tokio_postgres::GenericClient,
+    seconds: f64,
+) -> Result<u64, tokio_postgres::Error> {
+    let cancel_token = client.client().cancel_token();
+    let mut query = std::pin::pin!(async {
+        let stmt = client
+            .prepare_typed(SLEEP, &[tokio_postgres::types::Type::FLOAT8])
+            .await?;
+        client.execute(&stmt, &[&seconds]).await
+    });
+    match tokio::time::timeout(std::time::Duration::from_millis(100), &mut query).await {
+        Ok(result) => result,
+        Err(_) => {
+            cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+            query.await
+        }
+    }
+}
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sleep {
+    pub seconds: f64,
+}
+impl Sleep {
+    pub const QUERY: &'static str = r#"-- name: Sleep :exec
+SELECT pg_sleep($1::float8)"#;
+}
+impl Sleep {
+    pub async fn execute(
+        &self,
+        client: &impl tokio_postgres::GenericClient,
+    ) -> Result<u64, tokio_postgres::Error> {
+        let cancel_token = client.client().cancel_token();
+        let mut query = std::pin::pin!(async {
+            let stmt = client
+                .prepare_typed(Self::QUERY, &[tokio_postgres::types::Type::FLOAT8])
+                .await?;
+            client.execute(&stmt, &[&self.seconds]).await
+        });
+        match tokio::time::timeout(std::time::Duration::from_millis(100), &mut query).await {
+            Ok(result) => result,
+            Err(_) => {
+                cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                query.await
+            }
+        }
+    }
+}
+#[derive(Debug)]
+pub struct SleepBuilder<Fields = ()> {
+    fields: Fields,
+    phantom: std::marker::PhantomData<()>,
+}
+impl Sleep {
+    pub fn builder() -> SleepBuilder<()> {
+        SleepBuilder {
+            fields: (),
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl SleepBuilder<()> {
+    pub fn seconds(self, seconds: f64) -> SleepBuilder<f64> {
+        let () = self.fields;
+        SleepBuilder {
+            fields: seconds,
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl SleepBuilder<f64> {
+    pub fn build(self) -> Sleep {
+        let seconds = self.fields;
+        Sleep { seconds }
+    }
 }
@@ -60,4 +60,8 @@ SET title = $1, tags = $2, isbn = $4
 WHERE book_id = $3;
 
 -- name: SayHello :one
-select * from say_hello($1);
\ No newline at end of file
+select * from say_hello($1);
+
+-- name: Sleep :exec
+-- @timeout 100ms
+SELECT pg_sleep(sqlc.arg(seconds)::float8);
\ No newline at end of file
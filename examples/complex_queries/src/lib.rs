@@ -3,10 +3,28 @@ pub mod queries;
 #[cfg(test)]
 mod tests {
     use crate::queries::*;
+    use std::sync::{Arc, Mutex};
     use test_context::test_context;
     use test_utils::PgTokioTestContext;
     use tokio_postgres::NoTls;
 
+    type Finished = Arc<Mutex<Vec<(&'static str, &'static str, Option<u64>, bool)>>>;
+
+    struct RecordingObserver {
+        finished: Finished,
+    }
+
+    impl QueryObserver for RecordingObserver {
+        fn on_finish(&self, outcome: &QueryOutcome<'_>) {
+            self.finished.lock().unwrap().push((
+                outcome.name,
+                outcome.annotation,
+                outcome.rows,
+                outcome.error.is_some(),
+            ));
+        }
+    }
+
     #[test_context(PgTokioTestContext)]
     #[tokio::test]
     async fn test_complex_queries_compile(_ctx: &PgTokioTestContext) {
@@ -31,4 +49,114 @@ mod tests {
             let _result = get_top_rated_books(&client, Some(4)).await;
         }
     }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn query_observer_works(ctx: &mut PgTokioTestContext) {
+        let finished = Finished::default();
+        assert!(set_query_observer(RecordingObserver {
+            finished: finished.clone(),
+        })
+        .is_ok());
+
+        // Fails before the schema exists
+        assert!(get_category_stats(&ctx.client).await.is_err());
+
+        ctx.client
+            .batch_execute(include_str!("./schema.sql"))
+            .await
+            .unwrap();
+        let stats = get_category_stats(&ctx.client).await.unwrap();
+        assert_eq!(stats.count(), 0);
+
+        let finished = finished.lock().unwrap();
+        let finished = finished
+            .iter()
+            .filter(|(name, ..)| *name == "GetCategoryStats")
+            .collect::<Vec<_>>();
+        assert_eq!(
+            finished,
+            [
+                &("GetCategoryStats", ":many", None, true),
+                &("GetCategoryStats", ":many", Some(0), false),
+            ]
+        );
+    }
+
+    async fn seed_books(client: &tokio_postgres::Client) {
+        client
+            .batch_execute(include_str!("./schema.sql"))
+            .await
+            .unwrap();
+        client
+            .batch_execute(
+                "INSERT INTO authors (name) VALUES ('Ursula');
+                 INSERT INTO books (title, author_id, published_year) VALUES ('Earthsea', 1, 1968);
+                 INSERT INTO reviews (book_id, reviewer_name, rating) VALUES (1, 'Ged', 5);",
+            )
+            .await
+            .unwrap();
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn validate_columns_works(ctx: &mut PgTokioTestContext) {
+        seed_books(&ctx.client).await;
+
+        let books = get_top_rated_books(&ctx.client, Some(4))
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].title, "Earthsea");
+        assert_eq!(books[0].published_year, Some(1968));
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn validate_columns_accepts_reordered_columns(ctx: &mut PgTokioTestContext) {
+        seed_books(&ctx.client).await;
+
+        // `row_decoding: "name"` reads the columns by name, whatever their order
+        let row = ctx
+            .client
+            .query_one("SELECT published_year, title, id FROM books", &[])
+            .await
+            .unwrap();
+        let book = GetTopRatedBooksRow::from_row(&row).unwrap();
+        assert_eq!(book.title, "Earthsea");
+        assert_eq!(book.published_year, Some(1968));
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    #[should_panic(expected = "columns of GetTopRatedBooksRow changed")]
+    async fn validate_columns_detects_renamed_columns(ctx: &mut PgTokioTestContext) {
+        seed_books(&ctx.client).await;
+
+        let row = ctx
+            .client
+            .query_one("SELECT id, title AS name, published_year FROM books", &[])
+            .await
+            .unwrap();
+        let _ = GetTopRatedBooksRow::from_row(&row);
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    #[should_panic(expected = "type of column 2 of GetTopRatedBooksRow changed")]
+    async fn validate_columns_detects_changed_types(ctx: &mut PgTokioTestContext) {
+        seed_books(&ctx.client).await;
+
+        let row = ctx
+            .client
+            .query_one(
+                "SELECT id, title, published_year::bigint AS published_year FROM books",
+                &[],
+            )
+            .await
+            .unwrap();
+        let _ = GetTopRatedBooksRow::from_row(&row);
+    }
 }
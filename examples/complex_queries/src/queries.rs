@@ -1,6 +1,39 @@
 //! Code generated by sqlc. SHOULD NOT EDIT.
 //! sqlc version: v1.28.0
 //! sqlc-rust-postgres version: v0.1.4
+/// Outcome of one query execution
+#[derive(Debug)]
+pub struct QueryOutcome<'a> {
+    /// Query name such as `GetAuthor`
+    pub name: &'static str,
+    /// Query annotation such as `:one`
+    pub annotation: &'static str,
+    pub duration: std::time::Duration,
+    /// Rows returned, or affected by `:exec` queries, when the query succeeded
+    pub rows: Option<u64>,
+    pub error: Option<&'a tokio_postgres::Error>,
+}
+/// Hooks called around every generated query, e.g. to export metrics
+pub trait QueryObserver: Send + Sync {
+    fn on_start(&self, name: &'static str, annotation: &'static str) {
+        let _ = (name, annotation);
+    }
+    fn on_finish(&self, outcome: &QueryOutcome<'_>);
+}
+static QUERY_OBSERVER: std::sync::OnceLock<Box<dyn QueryObserver>> = std::sync::OnceLock::new();
+/// Installs the global `QueryObserver`
+///
+/// Returns the observer back if one was already installed. There is no
+/// per-client observer: the driver's `GenericClient` trait is sealed, so a
+/// client wrapper carrying one could not be passed to the query functions.
+pub fn set_query_observer<O: QueryObserver + 'static>(observer: O) -> Result<(), O> {
+    let mut observer = Some(observer);
+    QUERY_OBSERVER.get_or_init(|| Box::new(observer.take().unwrap()));
+    match observer {
+        Some(observer) => Err(observer),
+        None => Ok(()),
+    }
+}
 pub const GET_BOOK_WITH_AUTHOR_AND_CATEGORIES: &str = r#"-- name: GetBookWithAuthorAndCategories :many
 SELECT 
     b.id,
@@ -31,19 +64,106 @@ pub struct GetBookWithAuthorAndCategoriesRow {
 }
 impl GetBookWithAuthorAndCategoriesRow {
     pub(crate) fn from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        #[cfg(debug_assertions)]
+        {
+            let columns = row.columns();
+            let names = columns.iter().map(|c| c.name()).collect::<Vec<_>>();
+            debug_assert_eq!(
+                names,
+                [
+                    "id",
+                    "title",
+                    "published_year",
+                    "id",
+                    "name",
+                    "birth_year",
+                    "id",
+                    "name",
+                    "description"
+                ],
+                "columns of GetBookWithAuthorAndCategoriesRow changed since the code was generated"
+            );
+            let accepted = [
+                <i32 as tokio_postgres::types::FromSql>::accepts(columns[0].type_()),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "title")
+                    .is_some_and(|idx| {
+                        <String as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "published_year")
+                    .is_some_and(|idx| {
+                        <Option<i32> as tokio_postgres::types::FromSql>::accepts(
+                            columns[idx].type_(),
+                        )
+                    }),
+                <i32 as tokio_postgres::types::FromSql>::accepts(columns[3].type_()),
+                <String as tokio_postgres::types::FromSql>::accepts(columns[4].type_()),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "birth_year")
+                    .is_some_and(|idx| {
+                        <Option<i32> as tokio_postgres::types::FromSql>::accepts(
+                            columns[idx].type_(),
+                        )
+                    }),
+                <i32 as tokio_postgres::types::FromSql>::accepts(columns[6].type_()),
+                <String as tokio_postgres::types::FromSql>::accepts(columns[7].type_()),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "description")
+                    .is_some_and(|idx| {
+                        <Option<String> as tokio_postgres::types::FromSql>::accepts(
+                            columns[idx].type_(),
+                        )
+                    }),
+            ];
+            for (idx, accepted) in accepted.into_iter().enumerate() {
+                debug_assert!(
+                    accepted,
+                    "type of column {} of GetBookWithAuthorAndCategoriesRow changed since the code was generated",
+                    idx
+                );
+            }
+        }
         Ok(GetBookWithAuthorAndCategoriesRow {
             books_id: row.try_get(0)?,
-            title: row.try_get(1)?,
-            published_year: row.try_get(2)?,
+            title: row.try_get("title")?,
+            published_year: row.try_get("published_year")?,
             authors_id: row.try_get(3)?,
             authors_name: row.try_get(4)?,
-            birth_year: row.try_get(5)?,
+            birth_year: row.try_get("birth_year")?,
             categories_id: row.try_get(6)?,
             categories_name: row.try_get(7)?,
-            description: row.try_get(8)?,
+            description: row.try_get("description")?,
         })
     }
 }
+/// Test Case 1: Multiple table JOIN with potential column name conflicts
+///
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT
+///    b.id,
+///    b.title,
+///    b.published_year,
+///    a.id,
+///    a.name,
+///    a.birth_year,
+///    c.id,
+///    c.name,
+///    c.description
+/// FROM books b
+/// JOIN authors a ON b.author_id = a.id
+/// JOIN book_categories bc ON b.id = bc.book_id
+/// JOIN categories c ON bc.category_id = c.id
+/// WHERE b.published_year > $1
+/// ```
+///
+/// </details>
 pub async fn get_book_with_author_and_categories(
     client: &impl tokio_postgres::GenericClient,
     published_year: Option<i32>,
@@ -51,12 +171,34 @@ pub async fn get_book_with_author_and_categories(
     impl Iterator<Item = Result<GetBookWithAuthorAndCategoriesRow, tokio_postgres::Error>>,
     tokio_postgres::Error,
 > {
-    let rows = client
-        .query(GET_BOOK_WITH_AUTHOR_AND_CATEGORIES, &[&published_year])
-        .await?;
-    Ok(rows
-        .into_iter()
-        .map(|r| GetBookWithAuthorAndCategoriesRow::from_row(&r)))
+    let observer = QUERY_OBSERVER.get();
+    if let Some(observer) = observer {
+        observer.on_start("GetBookWithAuthorAndCategories", ":many");
+    }
+    let started_at = std::time::Instant::now();
+    let result = async {
+        let rows = client
+            .query(GET_BOOK_WITH_AUTHOR_AND_CATEGORIES, &[&published_year])
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| GetBookWithAuthorAndCategoriesRow::from_row(&r)))
+    }
+    .await;
+    if let Some(observer) = observer {
+        let (rows, error) = match &result {
+            Ok(value) => (Some(value.len() as u64), None),
+            Err(err) => (None, Some(err)),
+        };
+        observer.on_finish(&QueryOutcome {
+            name: "GetBookWithAuthorAndCategories",
+            annotation: ":many",
+            duration: started_at.elapsed(),
+            rows,
+            error,
+        });
+    }
+    result
 }
 #[derive(Debug)]
 pub struct GetBookWithAuthorAndCategories {
@@ -85,10 +227,32 @@ impl GetBookWithAuthorAndCategories {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Vec<GetBookWithAuthorAndCategoriesRow>, tokio_postgres::Error> {
-        let rows = client.query(Self::QUERY, &[&self.published_year]).await?;
-        rows.into_iter()
-            .map(|r| GetBookWithAuthorAndCategoriesRow::from_row(&r))
-            .collect()
+        let observer = QUERY_OBSERVER.get();
+        if let Some(observer) = observer {
+            observer.on_start("GetBookWithAuthorAndCategories", ":many");
+        }
+        let started_at = std::time::Instant::now();
+        let result = async {
+            let rows = client.query(Self::QUERY, &[&self.published_year]).await?;
+            rows.into_iter()
+                .map(|r| GetBookWithAuthorAndCategoriesRow::from_row(&r))
+                .collect::<Result<Vec<_>, _>>()
+        }
+        .await;
+        if let Some(observer) = observer {
+            let (rows, error) = match &result {
+                Ok(value) => (Some(value.len() as u64), None),
+                Err(err) => (None, Some(err)),
+            };
+            observer.on_finish(&QueryOutcome {
+                name: "GetBookWithAuthorAndCategories",
+                annotation: ":many",
+                duration: started_at.elapsed(),
+                rows,
+                error,
+            });
+        }
+        result
     }
     pub async fn query_raw(
         &self,
@@ -97,10 +261,32 @@ impl GetBookWithAuthorAndCategories {
         impl Iterator<Item = Result<GetBookWithAuthorAndCategoriesRow, tokio_postgres::Error>>,
         tokio_postgres::Error,
     > {
-        let rows = client.query(Self::QUERY, &[&self.published_year]).await?;
-        Ok(rows
-            .into_iter()
-            .map(|r| GetBookWithAuthorAndCategoriesRow::from_row(&r)))
+        let observer = QUERY_OBSERVER.get();
+        if let Some(observer) = observer {
+            observer.on_start("GetBookWithAuthorAndCategories", ":many");
+        }
+        let started_at = std::time::Instant::now();
+        let result = async {
+            let rows = client.query(Self::QUERY, &[&self.published_year]).await?;
+            Ok(rows
+                .into_iter()
+                .map(|r| GetBookWithAuthorAndCategoriesRow::from_row(&r)))
+        }
+        .await;
+        if let Some(observer) = observer {
+            let (rows, error) = match &result {
+                Ok(value) => (Some(value.len() as u64), None),
+                Err(err) => (None, Some(err)),
+            };
+            observer.on_finish(&QueryOutcome {
+                name: "GetBookWithAuthorAndCategories",
+                annotation: ":many",
+                duration: started_at.elapsed(),
+                rows,
+                error,
+            });
+        }
+        result
     }
 }
 #[derive(Debug)]
@@ -157,27 +343,108 @@ pub struct GetEmployeesWithManagersRow {
 }
 impl GetEmployeesWithManagersRow {
     pub(crate) fn from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        #[cfg(debug_assertions)]
+        {
+            let columns = row.columns();
+            let names = columns.iter().map(|c| c.name()).collect::<Vec<_>>();
+            debug_assert_eq!(
+                names,
+                [
+                    "id",
+                    "name",
+                    "department",
+                    "salary",
+                    "id",
+                    "name",
+                    "department"
+                ],
+                "columns of GetEmployeesWithManagersRow changed since the code was generated"
+            );
+            let accepted = [
+                <i32 as tokio_postgres::types::FromSql>::accepts(columns[0].type_()),
+                <String as tokio_postgres::types::FromSql>::accepts(columns[1].type_()),
+                <Option<String> as tokio_postgres::types::FromSql>::accepts(columns[2].type_()),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "salary")
+                    .is_some_and(|idx| {
+                        <Option<i32> as tokio_postgres::types::FromSql>::accepts(
+                            columns[idx].type_(),
+                        )
+                    }),
+                <Option<i32> as tokio_postgres::types::FromSql>::accepts(columns[4].type_()),
+                <Option<String> as tokio_postgres::types::FromSql>::accepts(columns[5].type_()),
+                <Option<String> as tokio_postgres::types::FromSql>::accepts(columns[6].type_()),
+            ];
+            for (idx, accepted) in accepted.into_iter().enumerate() {
+                debug_assert!(
+                    accepted,
+                    "type of column {} of GetEmployeesWithManagersRow changed since the code was generated",
+                    idx
+                );
+            }
+        }
         Ok(GetEmployeesWithManagersRow {
             employees_id_1: row.try_get(0)?,
             employees_name_1: row.try_get(1)?,
             employees_department_1: row.try_get(2)?,
-            salary: row.try_get(3)?,
+            salary: row.try_get("salary")?,
             employees_id_2: row.try_get(4)?,
             employees_name_2: row.try_get(5)?,
             employees_department_2: row.try_get(6)?,
         })
     }
 }
+/// Test Case 2: Self-join scenario
+///
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT
+///    e.id,
+///    e.name,
+///    e.department,
+///    e.salary,
+///    m.id,
+///    m.name,
+///    m.department
+/// FROM employees e
+/// LEFT JOIN employees m ON e.manager_id = m.id
+/// ```
+///
+/// </details>
 pub async fn get_employees_with_managers(
     client: &impl tokio_postgres::GenericClient,
 ) -> Result<
     impl Iterator<Item = Result<GetEmployeesWithManagersRow, tokio_postgres::Error>>,
     tokio_postgres::Error,
 > {
-    let rows = client.query(GET_EMPLOYEES_WITH_MANAGERS, &[]).await?;
-    Ok(rows
-        .into_iter()
-        .map(|r| GetEmployeesWithManagersRow::from_row(&r)))
+    let observer = QUERY_OBSERVER.get();
+    if let Some(observer) = observer {
+        observer.on_start("GetEmployeesWithManagers", ":many");
+    }
+    let started_at = std::time::Instant::now();
+    let result = async {
+        let rows = client.query(GET_EMPLOYEES_WITH_MANAGERS, &[]).await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| GetEmployeesWithManagersRow::from_row(&r)))
+    }
+    .await;
+    if let Some(observer) = observer {
+        let (rows, error) = match &result {
+            Ok(value) => (Some(value.len() as u64), None),
+            Err(err) => (None, Some(err)),
+        };
+        observer.on_finish(&QueryOutcome {
+            name: "GetEmployeesWithManagers",
+            annotation: ":many",
+            duration: started_at.elapsed(),
+            rows,
+            error,
+        });
+    }
+    result
 }
 pub const GET_TOP_RATED_BOOKS: &str = r#"-- name: GetTopRatedBooks :many
 SELECT id, title, published_year
@@ -195,13 +462,68 @@ pub struct GetTopRatedBooksRow {
 }
 impl GetTopRatedBooksRow {
     pub(crate) fn from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        #[cfg(debug_assertions)]
+        {
+            let columns = row.columns();
+            let mut names = columns.iter().map(|c| c.name()).collect::<Vec<_>>();
+            names.sort_unstable();
+            debug_assert_eq!(
+                names,
+                ["id", "published_year", "title"],
+                "columns of GetTopRatedBooksRow changed since the code was generated"
+            );
+            let accepted = [
+                columns
+                    .iter()
+                    .position(|c| c.name() == "id")
+                    .is_some_and(|idx| {
+                        <i32 as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "title")
+                    .is_some_and(|idx| {
+                        <String as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "published_year")
+                    .is_some_and(|idx| {
+                        <Option<i32> as tokio_postgres::types::FromSql>::accepts(
+                            columns[idx].type_(),
+                        )
+                    }),
+            ];
+            for (idx, accepted) in accepted.into_iter().enumerate() {
+                debug_assert!(
+                    accepted,
+                    "type of column {} of GetTopRatedBooksRow changed since the code was generated",
+                    idx
+                );
+            }
+        }
         Ok(GetTopRatedBooksRow {
-            id: row.try_get(0)?,
-            title: row.try_get(1)?,
-            published_year: row.try_get(2)?,
+            id: row.try_get("id")?,
+            title: row.try_get("title")?,
+            published_year: row.try_get("published_year")?,
         })
     }
 }
+/// Test Case 3: Subquery with single table (should use simple names)
+///
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, title, published_year
+/// FROM books
+/// WHERE id IN (
+///    SELECT book_id
+///    FROM reviews
+///    WHERE rating >= $1
+/// )
+/// ```
+///
+/// </details>
 pub async fn get_top_rated_books(
     client: &impl tokio_postgres::GenericClient,
     rating: Option<i32>,
@@ -209,8 +531,30 @@ pub async fn get_top_rated_books(
     impl Iterator<Item = Result<GetTopRatedBooksRow, tokio_postgres::Error>>,
     tokio_postgres::Error,
 > {
-    let rows = client.query(GET_TOP_RATED_BOOKS, &[&rating]).await?;
-    Ok(rows.into_iter().map(|r| GetTopRatedBooksRow::from_row(&r)))
+    let observer = QUERY_OBSERVER.get();
+    if let Some(observer) = observer {
+        observer.on_start("GetTopRatedBooks", ":many");
+    }
+    let started_at = std::time::Instant::now();
+    let result = async {
+        let rows = client.query(GET_TOP_RATED_BOOKS, &[&rating]).await?;
+        Ok(rows.into_iter().map(|r| GetTopRatedBooksRow::from_row(&r)))
+    }
+    .await;
+    if let Some(observer) = observer {
+        let (rows, error) = match &result {
+            Ok(value) => (Some(value.len() as u64), None),
+            Err(err) => (None, Some(err)),
+        };
+        observer.on_finish(&QueryOutcome {
+            name: "GetTopRatedBooks",
+            annotation: ":many",
+            duration: started_at.elapsed(),
+            rows,
+            error,
+        });
+    }
+    result
 }
 #[derive(Debug)]
 pub struct GetTopRatedBooks {
@@ -231,10 +575,32 @@ impl GetTopRatedBooks {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Vec<GetTopRatedBooksRow>, tokio_postgres::Error> {
-        let rows = client.query(Self::QUERY, &[&self.rating]).await?;
-        rows.into_iter()
-            .map(|r| GetTopRatedBooksRow::from_row(&r))
-            .collect()
+        let observer = QUERY_OBSERVER.get();
+        if let Some(observer) = observer {
+            observer.on_start("GetTopRatedBooks", ":many");
+        }
+        let started_at = std::time::Instant::now();
+        let result = async {
+            let rows = client.query(Self::QUERY, &[&self.rating]).await?;
+            rows.into_iter()
+                .map(|r| GetTopRatedBooksRow::from_row(&r))
+                .collect::<Result<Vec<_>, _>>()
+        }
+        .await;
+        if let Some(observer) = observer {
+            let (rows, error) = match &result {
+                Ok(value) => (Some(value.len() as u64), None),
+                Err(err) => (None, Some(err)),
+            };
+            observer.on_finish(&QueryOutcome {
+                name: "GetTopRatedBooks",
+                annotation: ":many",
+                duration: started_at.elapsed(),
+                rows,
+                error,
+            });
+        }
+        result
     }
     pub async fn query_raw(
         &self,
@@ -243,8 +609,30 @@ impl GetTopRatedBooks {
         impl Iterator<Item = Result<GetTopRatedBooksRow, tokio_postgres::Error>>,
         tokio_postgres::Error,
     > {
-        let rows = client.query(Self::QUERY, &[&self.rating]).await?;
-        Ok(rows.into_iter().map(|r| GetTopRatedBooksRow::from_row(&r)))
+        let observer = QUERY_OBSERVER.get();
+        if let Some(observer) = observer {
+            observer.on_start("GetTopRatedBooks", ":many");
+        }
+        let started_at = std::time::Instant::now();
+        let result = async {
+            let rows = client.query(Self::QUERY, &[&self.rating]).await?;
+            Ok(rows.into_iter().map(|r| GetTopRatedBooksRow::from_row(&r)))
+        }
+        .await;
+        if let Some(observer) = observer {
+            let (rows, error) = match &result {
+                Ok(value) => (Some(value.len() as u64), None),
+                Err(err) => (None, Some(err)),
+            };
+            observer.on_finish(&QueryOutcome {
+                name: "GetTopRatedBooks",
+                annotation: ":many",
+                duration: started_at.elapsed(),
+                rows,
+                error,
+            });
+        }
+        result
     }
 }
 #[derive(Debug)]
@@ -300,16 +688,84 @@ pub struct GetAuthorBookStatsRow {
 }
 impl GetAuthorBookStatsRow {
     pub(crate) fn from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        #[cfg(debug_assertions)]
+        {
+            let columns = row.columns();
+            let names = columns.iter().map(|c| c.name()).collect::<Vec<_>>();
+            debug_assert_eq!(
+                names,
+                ["id", "name", "book_count", "avg_rating", "id", "title"],
+                "columns of GetAuthorBookStatsRow changed since the code was generated"
+            );
+            let accepted = [
+                <i32 as tokio_postgres::types::FromSql>::accepts(columns[0].type_()),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "name")
+                    .is_some_and(|idx| {
+                        <String as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "book_count")
+                    .is_some_and(|idx| {
+                        <i64 as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "avg_rating")
+                    .is_some_and(|idx| {
+                        <f64 as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                <Option<i32> as tokio_postgres::types::FromSql>::accepts(columns[4].type_()),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "title")
+                    .is_some_and(|idx| {
+                        <Option<String> as tokio_postgres::types::FromSql>::accepts(
+                            columns[idx].type_(),
+                        )
+                    }),
+            ];
+            for (idx, accepted) in accepted.into_iter().enumerate() {
+                debug_assert!(
+                    accepted,
+                    "type of column {} of GetAuthorBookStatsRow changed since the code was generated",
+                    idx
+                );
+            }
+        }
         Ok(GetAuthorBookStatsRow {
             authors_id: row.try_get(0)?,
-            name: row.try_get(1)?,
-            book_count: row.try_get(2)?,
-            avg_rating: row.try_get(3)?,
+            name: row.try_get("name")?,
+            book_count: row.try_get("book_count")?,
+            avg_rating: row.try_get("avg_rating")?,
             books_id: row.try_get(4)?,
-            title: row.try_get(5)?,
+            title: row.try_get("title")?,
         })
     }
 }
+/// Test Case 4: Complex aggregation without CTE (simplified for sqlc compatibility)
+///
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT
+///    a.id,
+///    a.name,
+///    COUNT(DISTINCT b.id) as book_count,
+///    AVG(r.rating) as avg_rating,
+///    b.id,
+///    b.title
+/// FROM authors a
+/// LEFT JOIN books b ON a.id = b.author_id
+/// LEFT JOIN reviews r ON b.id = r.book_id
+/// WHERE b.id IS NOT NULL
+/// GROUP BY a.id, a.name, b.id, b.title
+/// HAVING COUNT(DISTINCT b.id) > $1
+/// ```
+///
+/// </details>
 pub async fn get_author_book_stats(
     client: &impl tokio_postgres::GenericClient,
     id: i32,
@@ -317,10 +773,32 @@ pub async fn get_author_book_stats(
     impl Iterator<Item = Result<GetAuthorBookStatsRow, tokio_postgres::Error>>,
     tokio_postgres::Error,
 > {
-    let rows = client.query(GET_AUTHOR_BOOK_STATS, &[&id]).await?;
-    Ok(rows
-        .into_iter()
-        .map(|r| GetAuthorBookStatsRow::from_row(&r)))
+    let observer = QUERY_OBSERVER.get();
+    if let Some(observer) = observer {
+        observer.on_start("GetAuthorBookStats", ":many");
+    }
+    let started_at = std::time::Instant::now();
+    let result = async {
+        let rows = client.query(GET_AUTHOR_BOOK_STATS, &[&id]).await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| GetAuthorBookStatsRow::from_row(&r)))
+    }
+    .await;
+    if let Some(observer) = observer {
+        let (rows, error) = match &result {
+            Ok(value) => (Some(value.len() as u64), None),
+            Err(err) => (None, Some(err)),
+        };
+        observer.on_finish(&QueryOutcome {
+            name: "GetAuthorBookStats",
+            annotation: ":many",
+            duration: started_at.elapsed(),
+            rows,
+            error,
+        });
+    }
+    result
 }
 #[derive(Debug)]
 pub struct GetAuthorBookStats {
@@ -347,10 +825,32 @@ impl GetAuthorBookStats {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Vec<GetAuthorBookStatsRow>, tokio_postgres::Error> {
-        let rows = client.query(Self::QUERY, &[&self.id]).await?;
-        rows.into_iter()
-            .map(|r| GetAuthorBookStatsRow::from_row(&r))
-            .collect()
+        let observer = QUERY_OBSERVER.get();
+        if let Some(observer) = observer {
+            observer.on_start("GetAuthorBookStats", ":many");
+        }
+        let started_at = std::time::Instant::now();
+        let result = async {
+            let rows = client.query(Self::QUERY, &[&self.id]).await?;
+            rows.into_iter()
+                .map(|r| GetAuthorBookStatsRow::from_row(&r))
+                .collect::<Result<Vec<_>, _>>()
+        }
+        .await;
+        if let Some(observer) = observer {
+            let (rows, error) = match &result {
+                Ok(value) => (Some(value.len() as u64), None),
+                Err(err) => (None, Some(err)),
+            };
+            observer.on_finish(&QueryOutcome {
+                name: "GetAuthorBookStats",
+                annotation: ":many",
+                duration: started_at.elapsed(),
+                rows,
+                error,
+            });
+        }
+        result
     }
     pub async fn query_raw(
         &self,
@@ -359,10 +859,32 @@ impl GetAuthorBookStats {
         impl Iterator<Item = Result<GetAuthorBookStatsRow, tokio_postgres::Error>>,
         tokio_postgres::Error,
     > {
-        let rows = client.query(Self::QUERY, &[&self.id]).await?;
-        Ok(rows
-            .into_iter()
-            .map(|r| GetAuthorBookStatsRow::from_row(&r)))
+        let observer = QUERY_OBSERVER.get();
+        if let Some(observer) = observer {
+            observer.on_start("GetAuthorBookStats", ":many");
+        }
+        let started_at = std::time::Instant::now();
+        let result = async {
+            let rows = client.query(Self::QUERY, &[&self.id]).await?;
+            Ok(rows
+                .into_iter()
+                .map(|r| GetAuthorBookStatsRow::from_row(&r)))
+        }
+        .await;
+        if let Some(observer) = observer {
+            let (rows, error) = match &result {
+                Ok(value) => (Some(value.len() as u64), None),
+                Err(err) => (None, Some(err)),
+            };
+            observer.on_finish(&QueryOutcome {
+                name: "GetAuthorBookStats",
+                annotation: ":many",
+                duration: started_at.elapsed(),
+                rows,
+                error,
+            });
+        }
+        result
     }
 }
 #[derive(Debug)]
@@ -418,6 +940,38 @@ pub struct CompareBookYearsRow {
 }
 impl CompareBookYearsRow {
     pub(crate) fn from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        #[cfg(debug_assertions)]
+        {
+            let columns = row.columns();
+            let names = columns.iter().map(|c| c.name()).collect::<Vec<_>>();
+            debug_assert_eq!(
+                names,
+                [
+                    "id",
+                    "title",
+                    "published_year",
+                    "id",
+                    "title",
+                    "published_year"
+                ],
+                "columns of CompareBookYearsRow changed since the code was generated"
+            );
+            let accepted = [
+                <i32 as tokio_postgres::types::FromSql>::accepts(columns[0].type_()),
+                <String as tokio_postgres::types::FromSql>::accepts(columns[1].type_()),
+                <Option<i32> as tokio_postgres::types::FromSql>::accepts(columns[2].type_()),
+                <i32 as tokio_postgres::types::FromSql>::accepts(columns[3].type_()),
+                <String as tokio_postgres::types::FromSql>::accepts(columns[4].type_()),
+                <Option<i32> as tokio_postgres::types::FromSql>::accepts(columns[5].type_()),
+            ];
+            for (idx, accepted) in accepted.into_iter().enumerate() {
+                debug_assert!(
+                    accepted,
+                    "type of column {} of CompareBookYearsRow changed since the code was generated",
+                    idx
+                );
+            }
+        }
         Ok(CompareBookYearsRow {
             books_id_1: row.try_get(0)?,
             books_title_1: row.try_get(1)?,
@@ -428,6 +982,27 @@ impl CompareBookYearsRow {
         })
     }
 }
+/// Test Case 5: Table aliases with same column names
+///
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT
+///    old_books.id,
+///    old_books.title,
+///    old_books.published_year,
+///    new_books.id,
+///    new_books.title,
+///    new_books.published_year
+/// FROM books old_books
+/// CROSS JOIN books new_books
+/// WHERE old_books.published_year < $1
+///  AND new_books.published_year > $2
+///  AND old_books.id != new_books.id
+/// LIMIT 10
+/// ```
+///
+/// </details>
 pub async fn compare_book_years(
     client: &impl tokio_postgres::GenericClient,
     published_year_1: Option<i32>,
@@ -436,10 +1011,32 @@ pub async fn compare_book_years(
     impl Iterator<Item = Result<CompareBookYearsRow, tokio_postgres::Error>>,
     tokio_postgres::Error,
 > {
-    let rows = client
-        .query(COMPARE_BOOK_YEARS, &[&published_year_1, &published_year_2])
-        .await?;
-    Ok(rows.into_iter().map(|r| CompareBookYearsRow::from_row(&r)))
+    let observer = QUERY_OBSERVER.get();
+    if let Some(observer) = observer {
+        observer.on_start("CompareBookYears", ":many");
+    }
+    let started_at = std::time::Instant::now();
+    let result = async {
+        let rows = client
+            .query(COMPARE_BOOK_YEARS, &[&published_year_1, &published_year_2])
+            .await?;
+        Ok(rows.into_iter().map(|r| CompareBookYearsRow::from_row(&r)))
+    }
+    .await;
+    if let Some(observer) = observer {
+        let (rows, error) = match &result {
+            Ok(value) => (Some(value.len() as u64), None),
+            Err(err) => (None, Some(err)),
+        };
+        observer.on_finish(&QueryOutcome {
+            name: "CompareBookYears",
+            annotation: ":many",
+            duration: started_at.elapsed(),
+            rows,
+            error,
+        });
+    }
+    result
 }
 #[derive(Debug)]
 pub struct CompareBookYears {
@@ -467,15 +1064,37 @@ impl CompareBookYears {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Vec<CompareBookYearsRow>, tokio_postgres::Error> {
-        let rows = client
-            .query(
-                Self::QUERY,
-                &[&self.published_year_1, &self.published_year_2],
-            )
-            .await?;
-        rows.into_iter()
-            .map(|r| CompareBookYearsRow::from_row(&r))
-            .collect()
+        let observer = QUERY_OBSERVER.get();
+        if let Some(observer) = observer {
+            observer.on_start("CompareBookYears", ":many");
+        }
+        let started_at = std::time::Instant::now();
+        let result = async {
+            let rows = client
+                .query(
+                    Self::QUERY,
+                    &[&self.published_year_1, &self.published_year_2],
+                )
+                .await?;
+            rows.into_iter()
+                .map(|r| CompareBookYearsRow::from_row(&r))
+                .collect::<Result<Vec<_>, _>>()
+        }
+        .await;
+        if let Some(observer) = observer {
+            let (rows, error) = match &result {
+                Ok(value) => (Some(value.len() as u64), None),
+                Err(err) => (None, Some(err)),
+            };
+            observer.on_finish(&QueryOutcome {
+                name: "CompareBookYears",
+                annotation: ":many",
+                duration: started_at.elapsed(),
+                rows,
+                error,
+            });
+        }
+        result
     }
     pub async fn query_raw(
         &self,
@@ -484,13 +1103,35 @@ impl CompareBookYears {
         impl Iterator<Item = Result<CompareBookYearsRow, tokio_postgres::Error>>,
         tokio_postgres::Error,
     > {
-        let rows = client
-            .query(
-                Self::QUERY,
-                &[&self.published_year_1, &self.published_year_2],
-            )
-            .await?;
-        Ok(rows.into_iter().map(|r| CompareBookYearsRow::from_row(&r)))
+        let observer = QUERY_OBSERVER.get();
+        if let Some(observer) = observer {
+            observer.on_start("CompareBookYears", ":many");
+        }
+        let started_at = std::time::Instant::now();
+        let result = async {
+            let rows = client
+                .query(
+                    Self::QUERY,
+                    &[&self.published_year_1, &self.published_year_2],
+                )
+                .await?;
+            Ok(rows.into_iter().map(|r| CompareBookYearsRow::from_row(&r)))
+        }
+        .await;
+        if let Some(observer) = observer {
+            let (rows, error) = match &result {
+                Ok(value) => (Some(value.len() as u64), None),
+                Err(err) => (None, Some(err)),
+            };
+            observer.on_finish(&QueryOutcome {
+                name: "CompareBookYears",
+                annotation: ":many",
+                duration: started_at.elapsed(),
+                rows,
+                error,
+            });
+        }
+        result
     }
 }
 #[derive(Debug)]
@@ -554,13 +1195,67 @@ pub struct GetBooksWithAliasesRow {
 }
 impl GetBooksWithAliasesRow {
     pub(crate) fn from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        #[cfg(debug_assertions)]
+        {
+            let columns = row.columns();
+            let mut names = columns.iter().map(|c| c.name()).collect::<Vec<_>>();
+            names.sort_unstable();
+            debug_assert_eq!(
+                names,
+                ["book_id", "book_title", "year"],
+                "columns of GetBooksWithAliasesRow changed since the code was generated"
+            );
+            let accepted = [
+                columns
+                    .iter()
+                    .position(|c| c.name() == "book_id")
+                    .is_some_and(|idx| {
+                        <i32 as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "book_title")
+                    .is_some_and(|idx| {
+                        <String as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "year")
+                    .is_some_and(|idx| {
+                        <Option<i32> as tokio_postgres::types::FromSql>::accepts(
+                            columns[idx].type_(),
+                        )
+                    }),
+            ];
+            for (idx, accepted) in accepted.into_iter().enumerate() {
+                debug_assert!(
+                    accepted,
+                    "type of column {} of GetBooksWithAliasesRow changed since the code was generated",
+                    idx
+                );
+            }
+        }
         Ok(GetBooksWithAliasesRow {
-            book_id: row.try_get(0)?,
-            book_title: row.try_get(1)?,
-            year: row.try_get(2)?,
+            book_id: row.try_get("book_id")?,
+            book_title: row.try_get("book_title")?,
+            year: row.try_get("year")?,
         })
     }
 }
+/// Test Case 6: Column aliases in single table (should still use simple names)
+///
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT
+///    id as book_id,
+///    title as book_title,
+///    published_year as year
+/// FROM books
+/// WHERE published_year BETWEEN $1 AND $2
+/// ```
+///
+/// </details>
 pub async fn get_books_with_aliases(
     client: &impl tokio_postgres::GenericClient,
     published_year_1: Option<i32>,
@@ -569,15 +1264,37 @@ pub async fn get_books_with_aliases(
     impl Iterator<Item = Result<GetBooksWithAliasesRow, tokio_postgres::Error>>,
     tokio_postgres::Error,
 > {
-    let rows = client
-        .query(
-            GET_BOOKS_WITH_ALIASES,
-            &[&published_year_1, &published_year_2],
-        )
-        .await?;
-    Ok(rows
-        .into_iter()
-        .map(|r| GetBooksWithAliasesRow::from_row(&r)))
+    let observer = QUERY_OBSERVER.get();
+    if let Some(observer) = observer {
+        observer.on_start("GetBooksWithAliases", ":many");
+    }
+    let started_at = std::time::Instant::now();
+    let result = async {
+        let rows = client
+            .query(
+                GET_BOOKS_WITH_ALIASES,
+                &[&published_year_1, &published_year_2],
+            )
+            .await?;
+        Ok(rows
+            .into_iter()
+            .map(|r| GetBooksWithAliasesRow::from_row(&r)))
+    }
+    .await;
+    if let Some(observer) = observer {
+        let (rows, error) = match &result {
+            Ok(value) => (Some(value.len() as u64), None),
+            Err(err) => (None, Some(err)),
+        };
+        observer.on_finish(&QueryOutcome {
+            name: "GetBooksWithAliases",
+            annotation: ":many",
+            duration: started_at.elapsed(),
+            rows,
+            error,
+        });
+    }
+    result
 }
 #[derive(Debug)]
 pub struct GetBooksWithAliases {
@@ -598,15 +1315,37 @@ impl GetBooksWithAliases {
         &self,
         client: &impl tokio_postgres::GenericClient,
     ) -> Result<Vec<GetBooksWithAliasesRow>, tokio_postgres::Error> {
-        let rows = client
-            .query(
-                Self::QUERY,
-                &[&self.published_year_1, &self.published_year_2],
-            )
-            .await?;
-        rows.into_iter()
-            .map(|r| GetBooksWithAliasesRow::from_row(&r))
-            .collect()
+        let observer = QUERY_OBSERVER.get();
+        if let Some(observer) = observer {
+            observer.on_start("GetBooksWithAliases", ":many");
+        }
+        let started_at = std::time::Instant::now();
+        let result = async {
+            let rows = client
+                .query(
+                    Self::QUERY,
+                    &[&self.published_year_1, &self.published_year_2],
+                )
+                .await?;
+            rows.into_iter()
+                .map(|r| GetBooksWithAliasesRow::from_row(&r))
+                .collect::<Result<Vec<_>, _>>()
+        }
+        .await;
+        if let Some(observer) = observer {
+            let (rows, error) = match &result {
+                Ok(value) => (Some(value.len() as u64), None),
+                Err(err) => (None, Some(err)),
+            };
+            observer.on_finish(&QueryOutcome {
+                name: "GetBooksWithAliases",
+                annotation: ":many",
+                duration: started_at.elapsed(),
+                rows,
+                error,
+            });
+        }
+        result
     }
     pub async fn query_raw(
         &self,
@@ -615,15 +1354,37 @@ impl GetBooksWithAliases {
         impl Iterator<Item = Result<GetBooksWithAliasesRow, tokio_postgres::Error>>,
         tokio_postgres::Error,
     > {
-        let rows = client
-            .query(
-                Self::QUERY,
-                &[&self.published_year_1, &self.published_year_2],
-            )
-            .await?;
-        Ok(rows
-            .into_iter()
-            .map(|r| GetBooksWithAliasesRow::from_row(&r)))
+        let observer = QUERY_OBSERVER.get();
+        if let Some(observer) = observer {
+            observer.on_start("GetBooksWithAliases", ":many");
+        }
+        let started_at = std::time::Instant::now();
+        let result = async {
+            let rows = client
+                .query(
+                    Self::QUERY,
+                    &[&self.published_year_1, &self.published_year_2],
+                )
+                .await?;
+            Ok(rows
+                .into_iter()
+                .map(|r| GetBooksWithAliasesRow::from_row(&r)))
+        }
+        .await;
+        if let Some(observer) = observer {
+            let (rows, error) = match &result {
+                Ok(value) => (Some(value.len() as u64), None),
+                Err(err) => (None, Some(err)),
+            };
+            observer.on_finish(&QueryOutcome {
+                name: "GetBooksWithAliases",
+                annotation: ":many",
+                duration: started_at.elapsed(),
+                rows,
+                error,
+            });
+        }
+        result
     }
 }
 #[derive(Debug)]
@@ -696,21 +1457,114 @@ pub struct GetCategoryStatsRow {
 }
 impl GetCategoryStatsRow {
     pub(crate) fn from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        #[cfg(debug_assertions)]
+        {
+            let columns = row.columns();
+            let mut names = columns.iter().map(|c| c.name()).collect::<Vec<_>>();
+            names.sort_unstable();
+            debug_assert_eq!(
+                names,
+                ["author_count", "avg_rating", "book_count", "id", "name"],
+                "columns of GetCategoryStatsRow changed since the code was generated"
+            );
+            let accepted = [
+                columns
+                    .iter()
+                    .position(|c| c.name() == "id")
+                    .is_some_and(|idx| {
+                        <i32 as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "name")
+                    .is_some_and(|idx| {
+                        <String as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "book_count")
+                    .is_some_and(|idx| {
+                        <i64 as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "author_count")
+                    .is_some_and(|idx| {
+                        <i64 as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+                columns
+                    .iter()
+                    .position(|c| c.name() == "avg_rating")
+                    .is_some_and(|idx| {
+                        <f64 as tokio_postgres::types::FromSql>::accepts(columns[idx].type_())
+                    }),
+            ];
+            for (idx, accepted) in accepted.into_iter().enumerate() {
+                debug_assert!(
+                    accepted,
+                    "type of column {} of GetCategoryStatsRow changed since the code was generated",
+                    idx
+                );
+            }
+        }
         Ok(GetCategoryStatsRow {
-            id: row.try_get(0)?,
-            name: row.try_get(1)?,
-            book_count: row.try_get(2)?,
-            author_count: row.try_get(3)?,
-            avg_rating: row.try_get(4)?,
+            id: row.try_get("id")?,
+            name: row.try_get("name")?,
+            book_count: row.try_get("book_count")?,
+            author_count: row.try_get("author_count")?,
+            avg_rating: row.try_get("avg_rating")?,
         })
     }
 }
+/// Test Case 7: Complex aggregation with multiple tables
+///
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT
+///    c.id,
+///    c.name,
+///    COUNT(DISTINCT b.id) as book_count,
+///    COUNT(DISTINCT a.id) as author_count,
+///    AVG(r.rating) as avg_rating
+/// FROM categories c
+/// LEFT JOIN book_categories bc ON c.id = bc.category_id
+/// LEFT JOIN books b ON bc.book_id = b.id
+/// LEFT JOIN authors a ON b.author_id = a.id
+/// LEFT JOIN reviews r ON b.id = r.book_id
+/// GROUP BY c.id, c.name
+/// HAVING COUNT(DISTINCT b.id) > 0
+/// ```
+///
+/// </details>
 pub async fn get_category_stats(
     client: &impl tokio_postgres::GenericClient,
 ) -> Result<
     impl Iterator<Item = Result<GetCategoryStatsRow, tokio_postgres::Error>>,
     tokio_postgres::Error,
 > {
-    let rows = client.query(GET_CATEGORY_STATS, &[]).await?;
-    Ok(rows.into_iter().map(|r| GetCategoryStatsRow::from_row(&r)))
+    let observer = QUERY_OBSERVER.get();
+    if let Some(observer) = observer {
+        observer.on_start("GetCategoryStats", ":many");
+    }
+    let started_at = std::time::Instant::now();
+    let result = async {
+        let rows = client.query(GET_CATEGORY_STATS, &[]).await?;
+        Ok(rows.into_iter().map(|r| GetCategoryStatsRow::from_row(&r)))
+    }
+    .await;
+    if let Some(observer) = observer {
+        let (rows, error) = match &result {
+            Ok(value) => (Some(value.len() as u64), None),
+            Err(err) => (None, Some(err)),
+        };
+        observer.on_finish(&QueryOutcome {
+            name: "GetCategoryStats",
+            annotation: ":many",
+            duration: started_at.elapsed(),
+            rows,
+            error,
+        });
+    }
+    result
 }
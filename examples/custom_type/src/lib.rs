@@ -46,4 +46,125 @@ mod tests {
         assert_eq!(first.voice_actor, voice_actor.into());
         assert_eq!(first.character, character.into());
     }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn query_directives_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+
+        // `@rust.derive(Default, Eq)`
+        let binaries = queries::get_binaries(&ctx.client)
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(binaries
+            .iter()
+            .all(|row| *row != queries::GetBinariesRow::default()));
+
+        // `@rust.visibility(pub(crate))`
+        let characters = queries::get_characters(&ctx.client).await.unwrap();
+        assert_eq!(characters.count(), 0);
+
+        // `@rust.deprecated("use GetNumerics")`
+        #[allow(deprecated)]
+        let bools = queries::get_bools(&ctx.client).await.unwrap();
+        assert_eq!(bools.count(), 0);
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn row_refs_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+        ctx.client
+            .batch_execute("INSERT INTO BinaryTable (col_bytea) VALUES ('\\x0102')")
+            .await
+            .unwrap();
+        queries::create_keyword(&ctx.client, "Foo", Some(1), None, None)
+            .await
+            .unwrap();
+
+        let rows = ctx.client.query(queries::GET_BINARIES, &[]).await.unwrap();
+        let binary = queries::GetBinariesRowRef::from_row(&rows[0]).unwrap();
+        assert_eq!(binary.col_bytea, Some(&[1u8, 2][..]));
+        assert_eq!(binary.to_owned().col_bytea, Some(vec![1, 2]));
+
+        let rows = ctx.client.query(queries::MATCH, &[&"Foo"]).await.unwrap();
+        let keyword = queries::MatchRowRef::from_row(&rows[0]).unwrap();
+        assert_eq!(keyword.r#type, "Foo");
+        let owned: queries::MatchRow = keyword.to_owned();
+        assert_eq!(owned.r#type, "Foo");
+        assert_eq!(owned.r#ref, Some(1));
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn keyword_idents_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+
+        let created = queries::create_keyword(
+            &ctx.client,
+            "Foo",
+            Some(1),
+            Some(true),
+            Some(queries::Podium::_1st),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(created.r#type, "Foo");
+        assert_eq!(created.self_, Some(true));
+
+        queries::CreateKeyword::builder()
+            .r#type("Bar")
+            .r#ref(None)
+            .self_(None)
+            .place(Some(queries::Podium::Self_))
+            .build()
+            .query_one(&ctx.client)
+            .await
+            .unwrap();
+
+        let rows = queries::r#match(&ctx.client, "Bar")
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].r#ref, None);
+        assert_eq!(rows[0].place, Some(queries::Podium::Self_));
+    }
+
+    #[test_context(PgTokioTestContext)]
+    #[tokio::test]
+    async fn unknown_enum_value_works(ctx: &mut PgTokioTestContext) {
+        migrate_db(&ctx.client).await;
+        ctx.client
+            .batch_execute("ALTER TYPE Podium ADD VALUE '3rd'")
+            .await
+            .unwrap();
+        ctx.client
+            .batch_execute("INSERT INTO KeywordTable (type, place) VALUES ('Foo', '3rd')")
+            .await
+            .unwrap();
+
+        let rows = queries::r#match(&ctx.client, "Foo")
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let place = rows[0].place.clone().unwrap();
+        assert_eq!(place, queries::Podium::Unknown("3rd".to_string()));
+        assert_eq!(place.to_string().parse::<queries::Podium>().unwrap(), place);
+
+        // Unknown values are written back with their label
+        let created = queries::create_keyword(&ctx.client, "Bar", None, None, Some(place))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            created.place,
+            Some(queries::Podium::Unknown("3rd".to_string()))
+        );
+    }
 }
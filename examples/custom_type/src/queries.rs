@@ -1,16 +1,166 @@
 //! Code generated by sqlc. SHOULD NOT EDIT.
 //! sqlc version: v1.28.0
 //! sqlc-rust-postgres version: v0.1.4
-#[derive(PartialEq, Debug, Clone, postgres_types::ToSql, postgres_types::FromSql)]
-#[postgres(name = "sponge_bob_character")]
+#[derive(PartialEq, Debug, Clone)]
+#[non_exhaustive]
 pub enum SpongeBobCharacter {
-    #[postgres(name = "Bob")]
     Bob,
-    #[postgres(name = "Patrick")]
     Patrick,
-    #[postgres(name = "Squidward")]
     Squidward,
+    /// Value added to the DB enum after this code was generated
+    Unknown(String),
+}
+impl<'a> postgres_types::FromSql<'a> for SpongeBobCharacter {
+    fn from_sql(
+        _: &postgres_types::Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(match std::str::from_utf8(raw)? {
+            "Bob" => Self::Bob,
+            "Patrick" => Self::Patrick,
+            "Squidward" => Self::Squidward,
+            label => Self::Unknown(label.to_string()),
+        })
+    }
+    fn accepts(ty: &postgres_types::Type) -> bool {
+        ty.name() == "sponge_bob_character" && matches!(ty.kind(), postgres_types::Kind::Enum(_))
+    }
+}
+impl postgres_types::ToSql for SpongeBobCharacter {
+    fn to_sql(
+        &self,
+        _: &postgres_types::Type,
+        out: &mut postgres_types::private::BytesMut,
+    ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        let label = match self {
+            Self::Bob => "Bob",
+            Self::Patrick => "Patrick",
+            Self::Squidward => "Squidward",
+            Self::Unknown(label) => label,
+        };
+        out.extend_from_slice(label.as_bytes());
+        Ok(postgres_types::IsNull::No)
+    }
+    fn accepts(ty: &postgres_types::Type) -> bool {
+        <Self as postgres_types::FromSql>::accepts(ty)
+    }
+    postgres_types::to_sql_checked!();
+}
+impl SpongeBobCharacter {
+    /// Every value in the order of the DB enum
+    pub const ALL: &'static [Self] = &[Self::Bob, Self::Patrick, Self::Squidward];
+    /// DB label of the value
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Bob => "Bob",
+            Self::Patrick => "Patrick",
+            Self::Squidward => "Squidward",
+            Self::Unknown(label) => label,
+        }
+    }
+}
+impl std::fmt::Display for SpongeBobCharacter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+impl std::str::FromStr for SpongeBobCharacter {
+    type Err = ParseEnumError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Bob" => Ok(Self::Bob),
+            "Patrick" => Ok(Self::Patrick),
+            "Squidward" => Ok(Self::Squidward),
+            _ => Ok(Self::Unknown(s.to_string())),
+        }
+    }
+}
+#[derive(PartialEq, Debug, Clone)]
+#[non_exhaustive]
+pub enum Podium {
+    _1st,
+    _2nd,
+    Self_,
+    /// Value added to the DB enum after this code was generated
+    Unknown(String),
+}
+impl<'a> postgres_types::FromSql<'a> for Podium {
+    fn from_sql(
+        _: &postgres_types::Type,
+        raw: &'a [u8],
+    ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(match std::str::from_utf8(raw)? {
+            "1st" => Self::_1st,
+            "2nd" => Self::_2nd,
+            "self" => Self::Self_,
+            label => Self::Unknown(label.to_string()),
+        })
+    }
+    fn accepts(ty: &postgres_types::Type) -> bool {
+        ty.name() == "podium" && matches!(ty.kind(), postgres_types::Kind::Enum(_))
+    }
+}
+impl postgres_types::ToSql for Podium {
+    fn to_sql(
+        &self,
+        _: &postgres_types::Type,
+        out: &mut postgres_types::private::BytesMut,
+    ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+        let label = match self {
+            Self::_1st => "1st",
+            Self::_2nd => "2nd",
+            Self::Self_ => "self",
+            Self::Unknown(label) => label,
+        };
+        out.extend_from_slice(label.as_bytes());
+        Ok(postgres_types::IsNull::No)
+    }
+    fn accepts(ty: &postgres_types::Type) -> bool {
+        <Self as postgres_types::FromSql>::accepts(ty)
+    }
+    postgres_types::to_sql_checked!();
+}
+impl Podium {
+    /// Every value in the order of the DB enum
+    pub const ALL: &'static [Self] = &[Self::_1st, Self::_2nd, Self::Self_];
+    /// DB label of the value
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::_1st => "1st",
+            Self::_2nd => "2nd",
+            Self::Self_ => "self",
+            Self::Unknown(label) => label,
+        }
+    }
+}
+impl std::fmt::Display for Podium {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+impl std::str::FromStr for Podium {
+    type Err = ParseEnumError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1st" => Ok(Self::_1st),
+            "2nd" => Ok(Self::_2nd),
+            "self" => Ok(Self::Self_),
+            _ => Ok(Self::Unknown(s.to_string())),
+        }
+    }
+}
+/// Error parsing an enum from a string which is not one of its DB labels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError {
+    pub type_name: &'static str,
+    pub value: String,
+}
+impl std::fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid {} value: `{}`", self.type_name, self.value)
+    }
 }
+impl std::error::Error for ParseEnumError {}
 pub const GET_BOOLS: &str = r#"-- name: GetBools :many
 SELECT col_bool, col_bool_alias, col_bool_array1, col_bool_array2
 FROM BoolTable"#;
@@ -31,6 +181,15 @@ impl GetBoolsRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT col_bool, col_bool_alias, col_bool_array1, col_bool_array2
+/// FROM BoolTable
+/// ```
+///
+/// </details>
+#[deprecated = "use GetNumerics"]
 pub async fn get_bools(
     client: &impl tokio_postgres::GenericClient,
 ) -> Result<impl Iterator<Item = Result<GetBoolsRow, tokio_postgres::Error>>, tokio_postgres::Error>
@@ -80,6 +239,14 @@ impl GetNumericsRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT col_smallint, col_smallint_alias, col_integer, col_integer_alias, col_int_alias, col_serial, col_bigint, col_bigint_alias, col_decimal, col_decimal_alias, col_real, col_real_alias, col_double_precision, col_double_precision_alias, col_money
+/// FROM NumericTable
+/// ```
+///
+/// </details>
 pub async fn get_numerics(
     client: &impl tokio_postgres::GenericClient,
 ) -> Result<
@@ -89,7 +256,7 @@ pub async fn get_numerics(
     let rows = client.query(GET_NUMERICS, &[]).await?;
     Ok(rows.into_iter().map(|r| GetNumericsRow::from_row(&r)))
 }
-pub const GET_CHARACTERS: &str = r#"-- name: GetCharacters :many
+pub(crate) const GET_CHARACTERS: &str = r#"-- name: GetCharacters :many
 SELECT col_char, col_char_alias, col_varchar, col_varchar_alias, col_text
 FROM CharacterTable"#;
 #[derive(PartialEq, Debug, Clone)]
@@ -111,7 +278,51 @@ impl GetCharactersRow {
         })
     }
 }
-pub async fn get_characters(
+#[derive(Debug, Clone)]
+pub struct GetCharactersRowRef<'r> {
+    pub col_char: Option<&'r str>,
+    pub col_char_alias: Option<&'r str>,
+    pub col_varchar: Option<&'r str>,
+    pub col_varchar_alias: Option<&'r str>,
+    pub col_text: Option<&'r str>,
+}
+impl<'r> GetCharactersRowRef<'r> {
+    pub(crate) fn from_row(row: &'r tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Ok(GetCharactersRowRef {
+            col_char: row.try_get(0)?,
+            col_char_alias: row.try_get(1)?,
+            col_varchar: row.try_get(2)?,
+            col_varchar_alias: row.try_get(3)?,
+            col_text: row.try_get(4)?,
+        })
+    }
+    /// Copies the borrowed values into an owned row
+    pub fn to_owned(&self) -> GetCharactersRow {
+        let Self {
+            col_char,
+            col_char_alias,
+            col_varchar,
+            col_varchar_alias,
+            col_text,
+        } = self.clone();
+        GetCharactersRow {
+            col_char: col_char.map(ToOwned::to_owned),
+            col_char_alias: col_char_alias.map(ToOwned::to_owned),
+            col_varchar: col_varchar.map(ToOwned::to_owned),
+            col_varchar_alias: col_varchar_alias.map(ToOwned::to_owned),
+            col_text: col_text.map(ToOwned::to_owned),
+        }
+    }
+}
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT col_char, col_char_alias, col_varchar, col_varchar_alias, col_text
+/// FROM CharacterTable
+/// ```
+///
+/// </details>
+pub(crate) async fn get_characters(
     client: &impl tokio_postgres::GenericClient,
 ) -> Result<
     impl Iterator<Item = Result<GetCharactersRow, tokio_postgres::Error>>,
@@ -123,7 +334,7 @@ pub async fn get_characters(
 pub const GET_BINARIES: &str = r#"-- name: GetBinaries :many
 SELECT col_bytea
 FROM BinaryTable"#;
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Debug, Clone, Default, Eq)]
 pub struct GetBinariesRow {
     pub col_bytea: Option<Vec<u8>>,
 }
@@ -134,6 +345,32 @@ impl GetBinariesRow {
         })
     }
 }
+#[derive(Debug, Clone)]
+pub struct GetBinariesRowRef<'r> {
+    pub col_bytea: Option<&'r [u8]>,
+}
+impl<'r> GetBinariesRowRef<'r> {
+    pub(crate) fn from_row(row: &'r tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Ok(GetBinariesRowRef {
+            col_bytea: row.try_get(0)?,
+        })
+    }
+    /// Copies the borrowed values into an owned row
+    pub fn to_owned(&self) -> GetBinariesRow {
+        let Self { col_bytea } = self.clone();
+        GetBinariesRow {
+            col_bytea: col_bytea.map(ToOwned::to_owned),
+        }
+    }
+}
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT col_bytea
+/// FROM BinaryTable
+/// ```
+///
+/// </details>
 pub async fn get_binaries(
     client: &impl tokio_postgres::GenericClient,
 ) -> Result<
@@ -159,6 +396,14 @@ impl GetCustomTypeRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT voice_actor, character
+/// FROM SpongeBobVoiceActor
+/// ```
+///
+/// </details>
 pub async fn get_custom_type(
     client: &impl tokio_postgres::GenericClient,
 ) -> Result<
@@ -186,6 +431,16 @@ impl CreateVoiceActorRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// INSERT INTO SpongeBobVoiceActor
+/// (voice_actor,character)
+/// VALUES ($1, $2)
+/// RETURNING voice_actor, character
+/// ```
+///
+/// </details>
 pub async fn create_voice_actor(
     client: &impl tokio_postgres::GenericClient,
     voice_actor: Option<&crate::VoiceActor>,
@@ -233,7 +488,7 @@ impl<'a> CreateVoiceActor<'a> {
             )
             .await?;
         match row {
-            Some(ref row) => Ok(Some(CreateVoiceActorRow::from_row(row)?)),
+            Some(row) => Ok(Some(CreateVoiceActorRow::from_row(&row)?)),
             None => Ok(None),
         }
     }
@@ -267,3 +522,287 @@ impl<'a> CreateVoiceActorBuilder<'a> {
         }
     }
 }
+pub const CREATE_KEYWORD: &str = r#"-- name: CreateKeyword :one
+INSERT INTO KeywordTable
+(type, ref, self, place)
+VALUES ($1, $2, $3, $4)
+RETURNING type, ref, self, place"#;
+#[derive(PartialEq, Debug, Clone)]
+pub struct CreateKeywordRow {
+    pub r#type: String,
+    pub r#ref: Option<i32>,
+    pub self_: Option<bool>,
+    pub place: Option<Podium>,
+}
+impl CreateKeywordRow {
+    pub(crate) fn from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Ok(CreateKeywordRow {
+            r#type: row.try_get(0)?,
+            r#ref: row.try_get(1)?,
+            self_: row.try_get(2)?,
+            place: row.try_get(3)?,
+        })
+    }
+}
+#[derive(Debug, Clone)]
+pub struct CreateKeywordRowRef<'r> {
+    pub r#type: &'r str,
+    pub r#ref: Option<i32>,
+    pub self_: Option<bool>,
+    pub place: Option<Podium>,
+}
+impl<'r> CreateKeywordRowRef<'r> {
+    pub(crate) fn from_row(row: &'r tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Ok(CreateKeywordRowRef {
+            r#type: row.try_get(0)?,
+            r#ref: row.try_get(1)?,
+            self_: row.try_get(2)?,
+            place: row.try_get(3)?,
+        })
+    }
+    /// Copies the borrowed values into an owned row
+    pub fn to_owned(&self) -> CreateKeywordRow {
+        let Self {
+            r#type,
+            r#ref,
+            self_,
+            place,
+        } = self.clone();
+        CreateKeywordRow {
+            r#type: r#type.to_owned(),
+            r#ref,
+            self_,
+            place,
+        }
+    }
+}
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// INSERT INTO KeywordTable
+/// (type, ref, self, place)
+/// VALUES ($1, $2, $3, $4)
+/// RETURNING type, ref, self, place
+/// ```
+///
+/// </details>
+pub async fn create_keyword(
+    client: &impl tokio_postgres::GenericClient,
+    r#type: &str,
+    r#ref: Option<i32>,
+    self_: Option<bool>,
+    place: Option<Podium>,
+) -> Result<Option<CreateKeywordRow>, tokio_postgres::Error> {
+    let query_struct = CreateKeyword {
+        r#type: std::borrow::Cow::Borrowed(r#type),
+        r#ref: r#ref,
+        self_: self_,
+        place: place,
+    };
+    query_struct.query_opt(client).await
+}
+#[derive(Debug)]
+pub struct CreateKeyword<'a> {
+    pub r#type: std::borrow::Cow<'a, str>,
+    pub r#ref: Option<i32>,
+    pub self_: Option<bool>,
+    pub place: Option<Podium>,
+}
+impl<'a> CreateKeyword<'a> {
+    pub const QUERY: &'static str = r#"-- name: CreateKeyword :one
+INSERT INTO KeywordTable
+(type, ref, self, place)
+VALUES ($1, $2, $3, $4)
+RETURNING type, ref, self, place"#;
+}
+impl<'a> CreateKeyword<'a> {
+    pub async fn query_one(
+        &self,
+        client: &impl tokio_postgres::GenericClient,
+    ) -> Result<CreateKeywordRow, tokio_postgres::Error> {
+        let row = client
+            .query_one(
+                Self::QUERY,
+                &[&self.r#type.as_ref(), &self.r#ref, &self.self_, &self.place],
+            )
+            .await?;
+        CreateKeywordRow::from_row(&row)
+    }
+    pub async fn query_opt(
+        &self,
+        client: &impl tokio_postgres::GenericClient,
+    ) -> Result<Option<CreateKeywordRow>, tokio_postgres::Error> {
+        let row = client
+            .query_opt(
+                Self::QUERY,
+                &[&self.r#type.as_ref(), &self.r#ref, &self.self_, &self.place],
+            )
+            .await?;
+        match row {
+            Some(row) => Ok(Some(CreateKeywordRow::from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+}
+#[derive(Debug, Default)]
+pub struct CreateKeywordBuilder<'a> {
+    r#type: Option<std::borrow::Cow<'a, str>>,
+    r#ref: Option<Option<i32>>,
+    self_: Option<Option<bool>>,
+    place: Option<Option<Podium>>,
+}
+impl<'a> CreateKeyword<'a> {
+    pub fn builder() -> CreateKeywordBuilder<'a> {
+        CreateKeywordBuilder::default()
+    }
+}
+impl<'a> CreateKeywordBuilder<'a> {
+    pub fn r#type<T>(mut self, r#type: T) -> Self
+    where
+        T: Into<std::borrow::Cow<'a, str>>,
+    {
+        self.r#type = Some(r#type.into());
+        self
+    }
+    pub fn r#ref(mut self, r#ref: Option<i32>) -> Self {
+        self.r#ref = Some(r#ref);
+        self
+    }
+    pub fn self_(mut self, self_: Option<bool>) -> Self {
+        self.self_ = Some(self_);
+        self
+    }
+    pub fn place(mut self, place: Option<Podium>) -> Self {
+        self.place = Some(place);
+        self
+    }
+    pub fn build(self) -> CreateKeyword<'a> {
+        CreateKeyword {
+            r#type: self.r#type.expect("Missing required field"),
+            r#ref: self.r#ref.expect("Missing required field"),
+            self_: self.self_.expect("Missing required field"),
+            place: self.place.expect("Missing required field"),
+        }
+    }
+}
+pub const MATCH: &str = r#"-- name: Match :many
+SELECT type, ref, self, place
+FROM KeywordTable
+WHERE type = $1"#;
+#[derive(PartialEq, Debug, Clone)]
+pub struct MatchRow {
+    pub r#type: String,
+    pub r#ref: Option<i32>,
+    pub self_: Option<bool>,
+    pub place: Option<Podium>,
+}
+impl MatchRow {
+    pub(crate) fn from_row(row: &tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Ok(MatchRow {
+            r#type: row.try_get(0)?,
+            r#ref: row.try_get(1)?,
+            self_: row.try_get(2)?,
+            place: row.try_get(3)?,
+        })
+    }
+}
+#[derive(Debug, Clone)]
+pub struct MatchRowRef<'r> {
+    pub r#type: &'r str,
+    pub r#ref: Option<i32>,
+    pub self_: Option<bool>,
+    pub place: Option<Podium>,
+}
+impl<'r> MatchRowRef<'r> {
+    pub(crate) fn from_row(row: &'r tokio_postgres::Row) -> Result<Self, tokio_postgres::Error> {
+        Ok(MatchRowRef {
+            r#type: row.try_get(0)?,
+            r#ref: row.try_get(1)?,
+            self_: row.try_get(2)?,
+            place: row.try_get(3)?,
+        })
+    }
+    /// Copies the borrowed values into an owned row
+    pub fn to_owned(&self) -> MatchRow {
+        let Self {
+            r#type,
+            r#ref,
+            self_,
+            place,
+        } = self.clone();
+        MatchRow {
+            r#type: r#type.to_owned(),
+            r#ref,
+            self_,
+            place,
+        }
+    }
+}
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT type, ref, self, place
+/// FROM KeywordTable
+/// WHERE type = $1
+/// ```
+///
+/// </details>
+pub async fn r#match(
+    client: &impl tokio_postgres::GenericClient,
+    r#type: &str,
+) -> Result<impl Iterator<Item = Result<MatchRow, tokio_postgres::Error>>, tokio_postgres::Error> {
+    let rows = client.query(MATCH, &[&r#type]).await?;
+    Ok(rows.into_iter().map(|r| MatchRow::from_row(&r)))
+}
+#[derive(Debug)]
+pub struct Match<'a> {
+    pub r#type: std::borrow::Cow<'a, str>,
+}
+impl<'a> Match<'a> {
+    pub const QUERY: &'static str = r#"-- name: Match :many
+SELECT type, ref, self, place
+FROM KeywordTable
+WHERE type = $1"#;
+}
+impl<'a> Match<'a> {
+    pub async fn query_many(
+        &self,
+        client: &impl tokio_postgres::GenericClient,
+    ) -> Result<Vec<MatchRow>, tokio_postgres::Error> {
+        let rows = client.query(Self::QUERY, &[&self.r#type.as_ref()]).await?;
+        rows.into_iter()
+            .map(|r| MatchRow::from_row(&r))
+            .collect::<Result<Vec<_>, _>>()
+    }
+    pub async fn query_raw(
+        &self,
+        client: &impl tokio_postgres::GenericClient,
+    ) -> Result<impl Iterator<Item = Result<MatchRow, tokio_postgres::Error>>, tokio_postgres::Error>
+    {
+        let rows = client.query(Self::QUERY, &[&self.r#type.as_ref()]).await?;
+        Ok(rows.into_iter().map(|r| MatchRow::from_row(&r)))
+    }
+}
+#[derive(Debug, Default)]
+pub struct MatchBuilder<'a> {
+    r#type: Option<std::borrow::Cow<'a, str>>,
+}
+impl<'a> Match<'a> {
+    pub fn builder() -> MatchBuilder<'a> {
+        MatchBuilder::default()
+    }
+}
+impl<'a> MatchBuilder<'a> {
+    pub fn r#type<T>(mut self, r#type: T) -> Self
+    where
+        T: Into<std::borrow::Cow<'a, str>>,
+    {
+        self.r#type = Some(r#type.into());
+        self
+    }
+    pub fn build(self) -> Match<'a> {
+        Match {
+            r#type: self.r#type.expect("Missing required field"),
+        }
+    }
+}
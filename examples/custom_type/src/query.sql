@@ -1,5 +1,6 @@
 
 -- name: GetBools :many
+-- @rust.deprecated("use GetNumerics")
 SELECT *
 FROM BoolTable;
 
@@ -8,10 +9,12 @@ SELECT *
 FROM NumericTable;
 
 -- name: GetCharacters :many
+-- @rust.visibility(pub(crate))
 SELECT *
 FROM CharacterTable;
 
 -- name: GetBinaries :many
+-- @rust.derive(Default, Eq)
 SELECT *
 FROM BinaryTable;
 
@@ -25,3 +28,14 @@ INSERT INTO SpongeBobVoiceActor
 (voice_actor,character)
 VALUES ($1, $2)
 RETURNING *;
+
+-- name: CreateKeyword :one
+INSERT INTO KeywordTable
+(type, ref, self, place)
+VALUES ($1, $2, $3, $4)
+RETURNING *;
+
+-- name: Match :many
+SELECT *
+FROM KeywordTable
+WHERE type = $1;
@@ -56,4 +56,16 @@ CREATE TABLE CharacterTable(
 
 CREATE TABLE BinaryTable(
     col_bytea               bytea
-);
\ No newline at end of file
+);
+CREATE TYPE Podium AS enum (
+    '1st',
+    '2nd',
+    'self'
+);
+
+CREATE TABLE KeywordTable(
+    type                    text NOT NULL,
+    ref                     integer,
+    self                    boolean,
+    place                   Podium
+);
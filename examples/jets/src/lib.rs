@@ -21,4 +21,142 @@ mod tests {
         let count = queries::count_pilots(&mut ctx.client).unwrap().unwrap();
         assert_eq!(count.count, 0)
     }
+
+    #[test_context(PgSyncTestContext)]
+    #[test]
+    fn query_timeout_restores_statement_timeout(ctx: &mut PgSyncTestContext) {
+        migrate_db(&mut ctx.client);
+        ctx.client
+            .batch_execute("SET statement_timeout = 7000")
+            .unwrap();
+
+        queries::count_pilots(&mut ctx.client).unwrap();
+        let mut transaction = ctx.client.transaction().unwrap();
+        queries::count_pilots(&mut transaction).unwrap();
+        let timeout: String = transaction
+            .query_one("SHOW statement_timeout", &[])
+            .unwrap()
+            .get(0);
+        assert_eq!(timeout, "7s");
+    }
+
+    #[test_context(PgSyncTestContext)]
+    #[test]
+    fn scalar_directive_works(ctx: &mut PgSyncTestContext) {
+        migrate_db(&mut ctx.client);
+        ctx.client
+            .batch_execute("INSERT INTO pilots (id, name) VALUES (1, 'Amelia')")
+            .unwrap();
+
+        let name: Option<String> = queries::get_pilot_name(&mut ctx.client, 1).unwrap();
+        assert_eq!(name.as_deref(), Some("Amelia"));
+        assert_eq!(queries::get_pilot_name(&mut ctx.client, 2).unwrap(), None);
+    }
+
+    #[test_context(PgSyncTestContext)]
+    #[test]
+    fn list_pilots_iter_works(ctx: &mut PgSyncTestContext) {
+        migrate_db(&mut ctx.client);
+        ctx.client
+            .batch_execute("INSERT INTO pilots (id, name) VALUES (1, 'Amelia'), (2, 'Bessie')")
+            .unwrap();
+
+        let names = queries::list_pilots_iter(&mut ctx.client)
+            .unwrap()
+            .map(|pilot| pilot.map(|pilot| pilot.name))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"Amelia".to_string()));
+    }
+
+    #[test_context(PgSyncTestContext)]
+    #[test]
+    fn list_pilots_cursor_works(ctx: &mut PgSyncTestContext) {
+        migrate_db(&mut ctx.client);
+        ctx.client
+            .batch_execute(
+                "INSERT INTO pilots (id, name) SELECT i, 'pilot' || i FROM generate_series(1, 5) i",
+            )
+            .unwrap();
+
+        let mut transaction = ctx.client.transaction().unwrap();
+        let batch_size = std::num::NonZeroU32::new(2).unwrap();
+        let mut cursor = queries::list_pilots_cursor(&mut transaction, batch_size).unwrap();
+        let mut batch_sizes = Vec::new();
+        loop {
+            let batch = cursor.next_batch().unwrap();
+            if batch.is_empty() {
+                break;
+            }
+            batch_sizes.push(batch.len());
+        }
+        assert_eq!(batch_sizes, vec![2, 2, 1]);
+        transaction.commit().unwrap();
+    }
+
+    #[test_context(PgSyncTestContext)]
+    #[test]
+    fn run_in_transaction_retries(ctx: &mut PgSyncTestContext) {
+        migrate_db(&mut ctx.client);
+
+        let options = queries::TransactionOptions {
+            isolation_level: Some(postgres::IsolationLevel::Serializable),
+            initial_backoff: std::time::Duration::from_millis(1),
+            ..Default::default()
+        };
+        let mut attempts = 0;
+        let count = queries::run_in_transaction(&mut ctx.client, &options, |transaction| {
+            attempts += 1;
+            transaction.execute("INSERT INTO pilots (id, name) VALUES (1, 'Amelia')", &[])?;
+            if attempts == 1 {
+                transaction.batch_execute(
+                    "DO $$ BEGIN RAISE EXCEPTION 'conflict' USING ERRCODE = 'serialization_failure'; END $$",
+                )?;
+            }
+            queries::count_pilots(transaction)
+        })
+        .unwrap()
+        .unwrap();
+        assert_eq!(attempts, 2);
+        assert_eq!(count.count, 1);
+
+        let options = queries::TransactionOptions {
+            max_retries: 0,
+            ..Default::default()
+        };
+        let err = queries::run_in_transaction(&mut ctx.client, &options, |transaction| {
+            queries::delete_pilot(transaction, 1)?;
+            transaction.batch_execute(
+                "DO $$ BEGIN RAISE EXCEPTION 'conflict' USING ERRCODE = 'deadlock_detected'; END $$",
+            )
+        })
+        .unwrap_err();
+        assert!(queries::TransactionError::is_retryable(&err));
+
+        // The failed transaction was rolled back
+        let count = queries::count_pilots(&mut ctx.client).unwrap().unwrap();
+        assert_eq!(count.count, 1);
+    }
+
+    #[test_context(PgSyncTestContext)]
+    #[test]
+    fn public_from_row_works(ctx: &mut PgSyncTestContext) {
+        migrate_db(&mut ctx.client);
+        ctx.client
+            .batch_execute("INSERT INTO pilots (id, name) VALUES (1, 'Amelia')")
+            .unwrap();
+
+        let rows = ctx
+            .client
+            .query("SELECT id, name FROM pilots WHERE id = $1", &[&1])
+            .unwrap();
+        let pilots = rows
+            .iter()
+            .map(queries::ListPilotsRow::from_row)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(pilots.len(), 1);
+        assert_eq!(pilots[0].name, "Amelia");
+    }
 }
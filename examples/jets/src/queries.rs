@@ -1,75 +1,277 @@
 //! Code generated by sqlc. SHOULD NOT EDIT.
 //! sqlc version: v1.28.0
 //! sqlc-rust-postgres version: v0.1.4
-pub const COUNT_PILOTS: &str = r#"-- name: CountPilots :one
+/// Server-side cursor yielding the rows of a `:many` query in batches
+pub(crate) struct RowCursor<'t, 'c, T> {
+    transaction: &'t mut postgres::Transaction<'c>,
+    portal: postgres::Portal,
+    batch_size: i32,
+    done: bool,
+    from_row: fn(&postgres::Row) -> Result<T, postgres::Error>,
+}
+impl<'t, 'c, T> RowCursor<'t, 'c, T> {
+    /// Fetches the next batch of rows; an empty batch means the cursor is exhausted
+    pub fn next_batch(&mut self) -> Result<Vec<T>, postgres::Error> {
+        if self.done {
+            return Ok(Vec::new());
+        }
+        let rows = self
+            .transaction
+            .query_portal(&self.portal, self.batch_size)?;
+        self.done = rows.len() < self.batch_size as usize;
+        rows.iter().map(self.from_row).collect()
+    }
+}
+/// Errors returned from a transaction closure
+pub(crate) trait TransactionError: From<postgres::Error> {
+    /// Whether the transaction may succeed when retried
+    fn is_retryable(&self) -> bool;
+}
+impl TransactionError for postgres::Error {
+    fn is_retryable(&self) -> bool {
+        self.code().is_some_and(|code| {
+            *code == postgres::error::SqlState::T_R_SERIALIZATION_FAILURE
+                || *code == postgres::error::SqlState::T_R_DEADLOCK_DETECTED
+        })
+    }
+}
+/// Options for `run_in_transaction`
+#[derive(Debug, Clone)]
+pub(crate) struct TransactionOptions {
+    /// Isolation level, or the server default when `None`
+    pub isolation_level: Option<postgres::IsolationLevel>,
+    pub read_only: bool,
+    /// Number of retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on every further retry
+    pub initial_backoff: std::time::Duration,
+    pub max_backoff: std::time::Duration,
+}
+impl Default for TransactionOptions {
+    fn default() -> Self {
+        Self {
+            isolation_level: None,
+            read_only: false,
+            max_retries: 3,
+            initial_backoff: std::time::Duration::from_millis(10),
+            max_backoff: std::time::Duration::from_secs(1),
+        }
+    }
+}
+/// Runs `f` in a transaction, retrying on serialization failures and deadlocks
+///
+/// The transaction is committed when `f` succeeds and rolled back otherwise.
+pub(crate) fn run_in_transaction<T, E, F>(
+    client: &mut postgres::Client,
+    options: &TransactionOptions,
+    mut f: F,
+) -> Result<T, E>
+where
+    E: TransactionError,
+    F: FnMut(&mut postgres::Transaction<'_>) -> Result<T, E>,
+{
+    let mut attempt = 0;
+    let mut backoff = options.initial_backoff;
+    loop {
+        if attempt > 0 {
+            std::thread::sleep(backoff);
+            backoff = backoff.saturating_mul(2).min(options.max_backoff);
+        }
+        let mut builder = client.build_transaction().read_only(options.read_only);
+        if let Some(isolation_level) = options.isolation_level {
+            builder = builder.isolation_level(isolation_level);
+        }
+        let result = match builder.start() {
+            Ok(mut transaction) => match f(&mut transaction) {
+                Ok(value) => transaction.commit().map(|_| value).map_err(E::from),
+                Err(err) => Err(err),
+            },
+            Err(err) => Err(E::from(err)),
+        };
+        match result {
+            Err(err) if err.is_retryable() && attempt < options.max_retries => {
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+const COUNT_PILOTS: &str = r#"-- name: CountPilots :one
 SELECT COUNT(*) FROM pilots"#;
 #[derive(Debug, Clone)]
-pub struct CountPilotsRow {
+pub(crate) struct CountPilotsRow {
     pub count: i64,
 }
 impl CountPilotsRow {
-    pub(crate) fn from_row(row: &postgres::Row) -> Result<Self, postgres::Error> {
+    pub fn from_row(row: &postgres::Row) -> Result<Self, postgres::Error> {
         Ok(CountPilotsRow {
             count: row.try_get(0)?,
         })
     }
 }
-pub fn count_pilots(
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT COUNT(*) FROM pilots
+/// ```
+///
+/// </details>
+pub(crate) fn count_pilots(
     client: &mut impl postgres::GenericClient,
 ) -> Result<Option<CountPilotsRow>, postgres::Error> {
-    let row = client.query_opt(COUNT_PILOTS, &[])?;
-    match row {
-        Some(ref row) => Ok(Some(CountPilotsRow::from_row(row)?)),
-        None => Ok(None),
-    }
+    let messages = client.simple_query("SHOW statement_timeout; SET statement_timeout = 5000")?;
+    let previous = messages
+        .iter()
+        .find_map(|message| match message {
+            postgres::SimpleQueryMessage::Row(row) => row.get(0),
+            _ => None,
+        })
+        .unwrap_or("0")
+        .replace('\'', "''");
+    let result = (|| {
+        let row = client.query_opt(COUNT_PILOTS, &[])?;
+        match row {
+            Some(row) => Ok(Some(CountPilotsRow::from_row(&row)?)),
+            None => Ok(None),
+        }
+    })();
+    let restored = client.batch_execute(&format!("SET statement_timeout = '{}'", previous));
+    result.and_then(|value| restored.map(|()| value))
 }
-pub const LIST_PILOTS: &str = r#"-- name: ListPilots :many
+const LIST_PILOTS: &str = r#"-- name: ListPilots :many
 SELECT id, name FROM pilots LIMIT 5"#;
 #[derive(Debug, Clone)]
-pub struct ListPilotsRow {
+pub(crate) struct ListPilotsRow {
     pub id: i32,
     pub name: String,
 }
 impl ListPilotsRow {
-    pub(crate) fn from_row(row: &postgres::Row) -> Result<Self, postgres::Error> {
+    pub fn from_row(row: &postgres::Row) -> Result<Self, postgres::Error> {
         Ok(ListPilotsRow {
             id: row.try_get(0)?,
             name: row.try_get(1)?,
         })
     }
 }
-pub fn list_pilots(
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, name FROM pilots LIMIT 5
+/// ```
+///
+/// </details>
+pub(crate) fn list_pilots(
     client: &mut impl postgres::GenericClient,
 ) -> Result<impl Iterator<Item = Result<ListPilotsRow, postgres::Error>>, postgres::Error> {
-    let rows = client.query(LIST_PILOTS, &[])?;
-    Ok(rows.into_iter().map(|r| ListPilotsRow::from_row(&r)))
+    let messages = client.simple_query("SHOW statement_timeout; SET statement_timeout = 5000")?;
+    let previous = messages
+        .iter()
+        .find_map(|message| match message {
+            postgres::SimpleQueryMessage::Row(row) => row.get(0),
+            _ => None,
+        })
+        .unwrap_or("0")
+        .replace('\'', "''");
+    let result = (|| {
+        let rows = client.query(LIST_PILOTS, &[])?;
+        Ok(rows.into_iter().map(|r| ListPilotsRow::from_row(&r)))
+    })();
+    let restored = client.batch_execute(&format!("SET statement_timeout = '{}'", previous));
+    result.and_then(|value| restored.map(|()| value))
 }
-pub const DELETE_PILOT: &str = r#"-- name: DeletePilot :exec
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, name FROM pilots LIMIT 5
+/// ```
+///
+/// </details>
+pub(crate) fn list_pilots_iter<'c>(
+    client: &'c mut impl postgres::GenericClient,
+) -> Result<impl Iterator<Item = Result<ListPilotsRow, postgres::Error>> + 'c, postgres::Error> {
+    let params: &[&(dyn postgres::types::ToSql + Sync)] = &[];
+    let rows = client.query_raw(LIST_PILOTS, params.iter().copied())?;
+    Ok(
+        postgres::fallible_iterator::FallibleIterator::iterator(rows)
+            .map(|row| row.and_then(|row| ListPilotsRow::from_row(&row))),
+    )
+}
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, name FROM pilots LIMIT 5
+/// ```
+///
+/// </details>
+pub(crate) fn list_pilots_cursor<'t, 'c>(
+    client: &'t mut postgres::Transaction<'c>,
+    batch_size: std::num::NonZeroU32,
+) -> Result<RowCursor<'t, 'c, ListPilotsRow>, postgres::Error> {
+    let portal = client.bind(LIST_PILOTS, &[])?;
+    Ok(RowCursor {
+        transaction: client,
+        portal,
+        batch_size: i32::try_from(batch_size.get()).unwrap_or(i32::MAX),
+        done: false,
+        from_row: ListPilotsRow::from_row,
+    })
+}
+const DELETE_PILOT: &str = r#"-- name: DeletePilot :exec
 DELETE FROM pilots WHERE id = $1"#;
-pub fn delete_pilot(
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// DELETE FROM pilots WHERE id = $1
+/// ```
+///
+/// </details>
+pub(crate) fn delete_pilot(
     client: &mut impl postgres::GenericClient,
     id: i32,
 ) -> Result<u64, postgres::Error> {
-    client.execute(DELETE_PILOT, &[&id])
+    let messages = client.simple_query("SHOW statement_timeout; SET statement_timeout = 5000")?;
+    let previous = messages
+        .iter()
+        .find_map(|message| match message {
+            postgres::SimpleQueryMessage::Row(row) => row.get(0),
+            _ => None,
+        })
+        .unwrap_or("0")
+        .replace('\'', "''");
+    let result = (|| client.execute(DELETE_PILOT, &[&id]))();
+    let restored = client.batch_execute(&format!("SET statement_timeout = '{}'", previous));
+    result.and_then(|value| restored.map(|()| value))
 }
 #[derive(Debug)]
-pub struct DeletePilot {
+pub(crate) struct DeletePilot {
     pub id: i32,
 }
 impl DeletePilot {
-    pub const QUERY: &'static str = r#"-- name: DeletePilot :exec
+    const QUERY: &'static str = r#"-- name: DeletePilot :exec
 DELETE FROM pilots WHERE id = $1"#;
 }
 impl DeletePilot {
-    pub async fn execute(
+    pub fn execute(
         &self,
         client: &mut impl postgres::GenericClient,
     ) -> Result<u64, postgres::Error> {
-        client.execute(Self::QUERY, &[&self.id])
+        let messages =
+            client.simple_query("SHOW statement_timeout; SET statement_timeout = 5000")?;
+        let previous = messages
+            .iter()
+            .find_map(|message| match message {
+                postgres::SimpleQueryMessage::Row(row) => row.get(0),
+                _ => None,
+            })
+            .unwrap_or("0")
+            .replace('\'', "''");
+        let result = (|| client.execute(Self::QUERY, &[&self.id]))();
+        let restored = client.batch_execute(&format!("SET statement_timeout = '{}'", previous));
+        result.and_then(|value| restored.map(|()| value))
     }
 }
 #[derive(Debug)]
-pub struct DeletePilotBuilder<Fields = ()> {
+pub(crate) struct DeletePilotBuilder<Fields = ()> {
     fields: Fields,
     phantom: std::marker::PhantomData<()>,
 }
@@ -96,3 +298,102 @@ impl DeletePilotBuilder<i32> {
         DeletePilot { id }
     }
 }
+const GET_PILOT_NAME: &str = r#"-- name: GetPilotName :one
+SELECT name FROM pilots WHERE id = $1"#;
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT name FROM pilots WHERE id = $1
+/// ```
+///
+/// </details>
+pub(crate) fn get_pilot_name(
+    client: &mut impl postgres::GenericClient,
+    id: i32,
+) -> Result<Option<String>, postgres::Error> {
+    let query_struct = GetPilotName { id: id };
+    query_struct.query_opt(client)
+}
+#[derive(Debug)]
+pub(crate) struct GetPilotName {
+    pub id: i32,
+}
+impl GetPilotName {
+    const QUERY: &'static str = r#"-- name: GetPilotName :one
+SELECT name FROM pilots WHERE id = $1"#;
+}
+impl GetPilotName {
+    pub fn query_one(
+        &self,
+        client: &mut impl postgres::GenericClient,
+    ) -> Result<String, postgres::Error> {
+        let messages =
+            client.simple_query("SHOW statement_timeout; SET statement_timeout = 5000")?;
+        let previous = messages
+            .iter()
+            .find_map(|message| match message {
+                postgres::SimpleQueryMessage::Row(row) => row.get(0),
+                _ => None,
+            })
+            .unwrap_or("0")
+            .replace('\'', "''");
+        let result = (|| {
+            let row = client.query_one(Self::QUERY, &[&self.id])?;
+            row.try_get(0)
+        })();
+        let restored = client.batch_execute(&format!("SET statement_timeout = '{}'", previous));
+        result.and_then(|value| restored.map(|()| value))
+    }
+    pub fn query_opt(
+        &self,
+        client: &mut impl postgres::GenericClient,
+    ) -> Result<Option<String>, postgres::Error> {
+        let messages =
+            client.simple_query("SHOW statement_timeout; SET statement_timeout = 5000")?;
+        let previous = messages
+            .iter()
+            .find_map(|message| match message {
+                postgres::SimpleQueryMessage::Row(row) => row.get(0),
+                _ => None,
+            })
+            .unwrap_or("0")
+            .replace('\'', "''");
+        let result = (|| {
+            let row = client.query_opt(Self::QUERY, &[&self.id])?;
+            match row {
+                Some(row) => Ok(Some(row.try_get(0)?)),
+                None => Ok(None),
+            }
+        })();
+        let restored = client.batch_execute(&format!("SET statement_timeout = '{}'", previous));
+        result.and_then(|value| restored.map(|()| value))
+    }
+}
+#[derive(Debug)]
+pub(crate) struct GetPilotNameBuilder<Fields = ()> {
+    fields: Fields,
+    phantom: std::marker::PhantomData<()>,
+}
+impl GetPilotName {
+    pub fn builder() -> GetPilotNameBuilder<()> {
+        GetPilotNameBuilder {
+            fields: (),
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl GetPilotNameBuilder<()> {
+    pub fn id(self, id: i32) -> GetPilotNameBuilder<i32> {
+        let () = self.fields;
+        GetPilotNameBuilder {
+            fields: id,
+            phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl GetPilotNameBuilder<i32> {
+    pub fn build(self) -> GetPilotName {
+        let id = self.fields;
+        GetPilotName { id }
+    }
+}
@@ -5,4 +5,8 @@ SELECT COUNT(*) FROM pilots;
 SELECT * FROM pilots LIMIT 5;
 
 -- name: DeletePilot :exec
-DELETE FROM pilots WHERE id = $1;
\ No newline at end of file
+DELETE FROM pilots WHERE id = $1;
+
+-- name: GetPilotName :one
+-- @rust.return(scalar)
+SELECT name FROM pilots WHERE id = $1;
\ No newline at end of file
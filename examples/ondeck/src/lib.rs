@@ -53,4 +53,186 @@ mod tests {
 
         assert_eq!(get_venue.id, venue.id);
     }
+
+    /// Runs any query, logging its name, and decodes the returned rows
+    async fn run<Q: queries::PgQuery>(
+        client: &deadpool_postgres::Client,
+        query: &Q,
+        log: &mut Vec<&'static str>,
+    ) -> Result<Vec<Q::Row>, deadpool_postgres::tokio_postgres::Error> {
+        log.push(Q::NAME);
+        client
+            .query(Q::SQL, &query.params())
+            .await?
+            .iter()
+            .map(Q::decode_row)
+            .collect()
+    }
+
+    #[test_context(DeadPoolContext)]
+    #[tokio::test]
+    async fn query_trait_works(ctx: &mut DeadPoolContext) {
+        use queries::PgQuery;
+
+        let mut client = ctx.pool.get().await.unwrap();
+        migrate_db(&mut client).await;
+        let mut log = Vec::new();
+
+        let create_city = queries::CreateCity {
+            name: "Berlin".into(),
+            slug: "berlin".into(),
+        };
+        let created = run(&client, &create_city, &mut log).await.unwrap();
+        assert_eq!(created[0].slug, "berlin");
+
+        let update = queries::UpdateCityName {
+            slug: "berlin".into(),
+            name: "Berlin, Germany".into(),
+        };
+        let updated = run(&client, &update, &mut log).await.unwrap();
+        assert!(updated.is_empty());
+
+        let city = run(
+            &client,
+            &queries::GetCity {
+                slug: "berlin".into(),
+            },
+            &mut log,
+        )
+        .await
+        .unwrap();
+        assert_eq!(city[0].name, "Berlin, Germany");
+
+        let cities = run(&client, &queries::ListCities, &mut log).await.unwrap();
+        assert_eq!(cities.len(), 1);
+
+        // borrowed, owned and missing `Cow` parameters
+        let statuses = [queries::Status::Open];
+        let create_venue = queries::CreateVenue {
+            slug: "berghain".into(),
+            name: "Berghain".into(),
+            city: "berlin".into(),
+            spotify_playlist: "spotify:berghain".into(),
+            status: queries::Status::Open,
+            statuses: Some(std::borrow::Cow::Borrowed(&statuses)),
+            tags: None,
+        };
+        let venue = run(&client, &create_venue, &mut log).await.unwrap();
+        let create_venue = queries::CreateVenue {
+            slug: "tresor".into(),
+            statuses: Some(std::borrow::Cow::Owned(vec![queries::Status::Closed])),
+            ..create_venue
+        };
+        let other_venue = run(&client, &create_venue, &mut log).await.unwrap();
+        assert_ne!(venue[0].id, other_venue[0].id);
+
+        assert_eq!(
+            log,
+            [
+                "CreateCity",
+                "UpdateCityName",
+                "GetCity",
+                "ListCities",
+                "CreateVenue",
+                "CreateVenue"
+            ]
+        );
+        assert_eq!(queries::GetCity::ANNOTATION, ":one");
+        assert_eq!(queries::GetCity::SQL, queries::GET_CITY);
+    }
+
+    #[test_context(DeadPoolContext)]
+    #[tokio::test]
+    async fn prepared_queries_works(ctx: &mut DeadPoolContext) {
+        let mut client = ctx.pool.get().await.unwrap();
+        migrate_db(&mut client).await;
+
+        let prepared = queries::PreparedQueries::prepare(&client).await.unwrap();
+
+        let city = prepared
+            .create_city(&client, "Tokyo", "tokyo")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(city.slug, "tokyo");
+
+        let get_city = prepared.get_city(&client, "tokyo").await.unwrap().unwrap();
+        assert_eq!(get_city.name, "Tokyo");
+
+        let updated = prepared
+            .update_city_name(&client, "tokyo", "Edo")
+            .await
+            .unwrap();
+        assert_eq!(updated, 1);
+
+        let cities = prepared
+            .list_cities(&client)
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(cities.len(), 1);
+        assert_eq!(cities[0].name, "Edo");
+
+        // Preparing again on the same connection hits the statement cache
+        let cached = queries::PreparedQueries::prepare(&client).await.unwrap();
+        assert!(cached.get_city(&client, "tokyo").await.unwrap().is_some());
+    }
+
+    #[test_context(DeadPoolContext)]
+    #[tokio::test]
+    async fn run_in_transaction_works(ctx: &mut DeadPoolContext) {
+        let mut client = ctx.pool.get().await.unwrap();
+        migrate_db(&mut client).await;
+
+        let options = queries::TransactionOptions {
+            isolation_level: Some(deadpool_postgres::tokio_postgres::IsolationLevel::Serializable),
+            initial_backoff: std::time::Duration::from_millis(1),
+            ..Default::default()
+        };
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        let city = queries::run_in_transaction(&mut client, &options, |transaction| {
+            let attempt = attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Box::pin(async move {
+                let city = queries::create_city(transaction, "Osaka", "osaka").await?;
+                if attempt == 0 {
+                    transaction
+                        .batch_execute(
+                            "DO $$ BEGIN RAISE EXCEPTION 'conflict' USING ERRCODE = 'serialization_failure'; END $$",
+                        )
+                        .await?;
+                }
+                Ok::<_, deadpool_postgres::tokio_postgres::Error>(city)
+            })
+        })
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(city.slug, "osaka");
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        let cities = queries::list_cities(&client)
+            .await
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(cities.len(), 1);
+    }
+
+    #[test]
+    fn enum_helpers_works() {
+        let labels = queries::Status::ALL
+            .iter()
+            .map(queries::Status::as_str)
+            .collect::<Vec<_>>();
+        assert_eq!(labels, vec!["op!en", "clo@sed"]);
+        assert_eq!(queries::Status::Closed.to_string(), "clo@sed");
+
+        let status = "op!en".parse::<queries::Status>().unwrap();
+        assert_eq!(status, queries::Status::Open);
+
+        let err = "open".parse::<queries::Status>().unwrap_err();
+        assert_eq!(err.type_name, "Status");
+        assert_eq!(err.to_string(), "invalid Status value: `open`");
+    }
 }
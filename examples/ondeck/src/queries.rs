@@ -1,7 +1,8 @@
 //! Code generated by sqlc. SHOULD NOT EDIT.
 //! sqlc version: v1.28.0
 //! sqlc-rust-postgres version: v0.1.4
-#[derive(Debug, Clone, postgres_types::ToSql, postgres_types::FromSql)]
+/// Venues can be either open or closed
+#[derive(Debug, Clone, postgres_types::ToSql, postgres_types::FromSql, PartialEq, Eq, Hash)]
 #[postgres(name = "status")]
 pub enum Status {
     #[postgres(name = "op!en")]
@@ -9,6 +10,137 @@ pub enum Status {
     #[postgres(name = "clo@sed")]
     Closed,
 }
+impl Status {
+    /// Every value in the order of the DB enum
+    pub const ALL: &'static [Self] = &[Self::Open, Self::Closed];
+    /// DB label of the value
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "op!en",
+            Self::Closed => "clo@sed",
+        }
+    }
+}
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+impl std::str::FromStr for Status {
+    type Err = ParseEnumError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "op!en" => Ok(Self::Open),
+            "clo@sed" => Ok(Self::Closed),
+            _ => Err(ParseEnumError {
+                type_name: "Status",
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+/// Error parsing an enum from a string which is not one of its DB labels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseEnumError {
+    pub type_name: &'static str,
+    pub value: String,
+}
+impl std::fmt::Display for ParseEnumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "invalid {} value: `{}`", self.type_name, self.value)
+    }
+}
+impl std::error::Error for ParseEnumError {}
+/// Looks up a database type such as an enum or composite by its quoted name
+async fn lookup_type(
+    client: &impl deadpool_postgres::GenericClient,
+    type_name: &str,
+) -> Result<deadpool_postgres::tokio_postgres::types::Type, deadpool_postgres::tokio_postgres::Error>
+{
+    let stmt = client
+        .prepare_cached(&format!("SELECT NULL::{}", type_name))
+        .await?;
+    Ok(stmt.columns()[0].type_().clone())
+}
+/// Errors returned from a transaction closure
+pub trait TransactionError: From<deadpool_postgres::tokio_postgres::Error> {
+    /// Whether the transaction may succeed when retried
+    fn is_retryable(&self) -> bool;
+}
+impl TransactionError for deadpool_postgres::tokio_postgres::Error {
+    fn is_retryable(&self) -> bool {
+        self.code().is_some_and(|code| {
+            *code == deadpool_postgres::tokio_postgres::error::SqlState::T_R_SERIALIZATION_FAILURE
+                || *code
+                    == deadpool_postgres::tokio_postgres::error::SqlState::T_R_DEADLOCK_DETECTED
+        })
+    }
+}
+/// Options for `run_in_transaction`
+#[derive(Debug, Clone)]
+pub struct TransactionOptions {
+    /// Isolation level, or the server default when `None`
+    pub isolation_level: Option<deadpool_postgres::tokio_postgres::IsolationLevel>,
+    pub read_only: bool,
+    /// Number of retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled on every further retry
+    pub initial_backoff: std::time::Duration,
+    pub max_backoff: std::time::Duration,
+}
+impl Default for TransactionOptions {
+    fn default() -> Self {
+        Self {
+            isolation_level: None,
+            read_only: false,
+            max_retries: 3,
+            initial_backoff: std::time::Duration::from_millis(10),
+            max_backoff: std::time::Duration::from_secs(1),
+        }
+    }
+}
+/// Runs `f` in a transaction, retrying on serialization failures and deadlocks
+///
+/// The transaction is committed when `f` succeeds and rolled back otherwise.
+pub async fn run_in_transaction<T, E, F>(
+    client: &mut deadpool_postgres::Client,
+    options: &TransactionOptions,
+    mut f: F,
+) -> Result<T, E>
+where
+    E: TransactionError,
+    F: for<'t> FnMut(
+        &'t deadpool_postgres::Transaction<'_>,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = Result<T, E>> + Send + 't>,
+    >,
+{
+    let mut attempt = 0;
+    let mut backoff = options.initial_backoff;
+    loop {
+        if attempt > 0 {
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2).min(options.max_backoff);
+        }
+        let mut builder = client.build_transaction().read_only(options.read_only);
+        if let Some(isolation_level) = options.isolation_level {
+            builder = builder.isolation_level(isolation_level);
+        }
+        let result = match builder.start().await {
+            Ok(transaction) => match f(&transaction).await {
+                Ok(value) => transaction.commit().await.map(|_| value).map_err(E::from),
+                Err(err) => Err(err),
+            },
+            Err(err) => Err(E::from(err)),
+        };
+        match result {
+            Err(err) if err.is_retryable() && attempt < options.max_retries => {
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
 pub const LIST_CITIES: &str = r#"-- name: ListCities :many
 SELECT slug, name
 FROM city
@@ -28,6 +160,15 @@ impl ListCitiesRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT slug, name
+/// FROM city
+/// ORDER BY name
+/// ```
+///
+/// </details>
 pub async fn list_cities(
     client: &impl deadpool_postgres::GenericClient,
 ) -> Result<
@@ -37,6 +178,8 @@ pub async fn list_cities(
     let rows = client.query(LIST_CITIES, &[]).await?;
     Ok(rows.into_iter().map(|r| ListCitiesRow::from_row(&r)))
 }
+#[derive(Debug)]
+pub struct ListCities;
 pub const GET_CITY: &str = r#"-- name: GetCity :one
 SELECT slug, name
 FROM city
@@ -56,6 +199,15 @@ impl GetCityRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT slug, name
+/// FROM city
+/// WHERE slug = $1
+/// ```
+///
+/// </details>
 pub async fn get_city(
     client: &impl deadpool_postgres::GenericClient,
     slug: &str,
@@ -93,7 +245,7 @@ impl<'a> GetCity<'a> {
             .query_opt(Self::QUERY, &[&self.slug.as_ref()])
             .await?;
         match row {
-            Some(ref row) => Ok(Some(GetCityRow::from_row(row)?)),
+            Some(row) => Ok(Some(GetCityRow::from_row(&row)?)),
             None => Ok(None),
         }
     }
@@ -144,6 +296,23 @@ impl CreateCityRow {
         })
     }
 }
+/// Create a new city. The slug must be unique.
+/// This is the second line of the comment
+/// This is the third line
+///
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// INSERT INTO city (
+///    name,
+///    slug
+/// ) VALUES (
+///    $1,
+///    $2
+/// ) RETURNING slug, name
+/// ```
+///
+/// </details>
 pub async fn create_city(
     client: &impl deadpool_postgres::GenericClient,
     name: &str,
@@ -188,7 +357,7 @@ impl<'a> CreateCity<'a> {
             .query_opt(Self::QUERY, &[&self.name.as_ref(), &self.slug.as_ref()])
             .await?;
         match row {
-            Some(ref row) => Ok(Some(CreateCityRow::from_row(row)?)),
+            Some(row) => Ok(Some(CreateCityRow::from_row(&row)?)),
             None => Ok(None),
         }
     }
@@ -229,6 +398,15 @@ pub const UPDATE_CITY_NAME: &str = r#"-- name: UpdateCityName :exec
 UPDATE city
 SET name = $2
 WHERE slug = $1"#;
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// UPDATE city
+/// SET name = $2
+/// WHERE slug = $1
+/// ```
+///
+/// </details>
 pub async fn update_city_name(
     client: &impl deadpool_postgres::GenericClient,
     slug: &str,
@@ -294,11 +472,13 @@ SELECT id, status, statuses, slug, name, city, spotify_playlist, songkick_id, ta
 FROM venue
 WHERE city = $1
 ORDER BY name"#;
+/// Venues are places where muisc happens
 #[derive(Debug, Clone)]
 pub struct ListVenuesRow {
     pub id: i32,
     pub status: Status,
     pub statuses: Option<Vec<Status>>,
+    /// This value appears in public URLs
     pub slug: String,
     pub name: String,
     pub city: String,
@@ -325,6 +505,16 @@ impl ListVenuesRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, status, statuses, slug, name, city, spotify_playlist, songkick_id, tags, created_at
+/// FROM venue
+/// WHERE city = $1
+/// ORDER BY name
+/// ```
+///
+/// </details>
 pub async fn list_venues(
     client: &impl deadpool_postgres::GenericClient,
     city: &str,
@@ -354,7 +544,7 @@ impl<'a> ListVenues<'a> {
         let rows = client.query(Self::QUERY, &[&self.city.as_ref()]).await?;
         rows.into_iter()
             .map(|r| ListVenuesRow::from_row(&r))
-            .collect()
+            .collect::<Result<Vec<_>, _>>()
     }
     pub async fn query_raw(
         &self,
@@ -393,6 +583,14 @@ impl<'a> ListVenuesBuilder<'a> {
 pub const DELETE_VENUE: &str = r#"-- name: DeleteVenue :exec
 DELETE FROM venue
 WHERE slug = $1 AND slug = $1"#;
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// DELETE FROM venue
+/// WHERE slug = $1 AND slug = $1
+/// ```
+///
+/// </details>
 pub async fn delete_venue(
     client: &impl deadpool_postgres::GenericClient,
     slug: &str,
@@ -443,11 +641,13 @@ pub const GET_VENUE: &str = r#"-- name: GetVenue :one
 SELECT id, status, statuses, slug, name, city, spotify_playlist, songkick_id, tags, created_at
 FROM venue
 WHERE slug = $1 AND city = $2"#;
+/// Venues are places where muisc happens
 #[derive(Debug, Clone)]
 pub struct GetVenueRow {
     pub id: i32,
     pub status: Status,
     pub statuses: Option<Vec<Status>>,
+    /// This value appears in public URLs
     pub slug: String,
     pub name: String,
     pub city: String,
@@ -474,6 +674,15 @@ impl GetVenueRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT id, status, statuses, slug, name, city, spotify_playlist, songkick_id, tags, created_at
+/// FROM venue
+/// WHERE slug = $1 AND city = $2
+/// ```
+///
+/// </details>
 pub async fn get_venue(
     client: &impl deadpool_postgres::GenericClient,
     slug: &str,
@@ -514,7 +723,7 @@ impl<'a> GetVenue<'a> {
             .query_opt(Self::QUERY, &[&self.slug.as_ref(), &self.city.as_ref()])
             .await?;
         match row {
-            Some(ref row) => Ok(Some(GetVenueRow::from_row(row)?)),
+            Some(row) => Ok(Some(GetVenueRow::from_row(&row)?)),
             None => Ok(None),
         }
     }
@@ -571,6 +780,7 @@ INSERT INTO venue (
     $6,
     $7
 ) RETURNING id"#;
+/// Venues are places where muisc happens
 #[derive(Debug, Clone)]
 pub struct CreateVenueRow {
     pub id: i32,
@@ -584,6 +794,31 @@ impl CreateVenueRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// INSERT INTO venue (
+///    slug,
+///    name,
+///    city,
+///    created_at,
+///    spotify_playlist,
+///    status,
+///    statuses,
+///    tags
+/// ) VALUES (
+///    $1,
+///    $2,
+///    $3,
+///    NOW(),
+///    $4,
+///    $5,
+///    $6,
+///    $7
+/// ) RETURNING id
+/// ```
+///
+/// </details>
 pub async fn create_venue(
     client: &impl deadpool_postgres::GenericClient,
     slug: &str,
@@ -677,7 +912,7 @@ impl<'a> CreateVenue<'a> {
             )
             .await?;
         match row {
-            Some(ref row) => Ok(Some(CreateVenueRow::from_row(row)?)),
+            Some(row) => Ok(Some(CreateVenueRow::from_row(&row)?)),
             None => Ok(None),
         }
     }
@@ -761,6 +996,7 @@ UPDATE venue
 SET name = $2
 WHERE slug = $1
 RETURNING id"#;
+/// Venues are places where muisc happens
 #[derive(Debug, Clone)]
 pub struct UpdateVenueNameRow {
     pub id: i32,
@@ -774,6 +1010,16 @@ impl UpdateVenueNameRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// UPDATE venue
+/// SET name = $2
+/// WHERE slug = $1
+/// RETURNING id
+/// ```
+///
+/// </details>
 pub async fn update_venue_name(
     client: &impl deadpool_postgres::GenericClient,
     slug: &str,
@@ -815,7 +1061,7 @@ impl<'a> UpdateVenueName<'a> {
             .query_opt(Self::QUERY, &[&self.slug.as_ref(), &self.name.as_ref()])
             .await?;
         match row {
-            Some(ref row) => Ok(Some(UpdateVenueNameRow::from_row(row)?)),
+            Some(row) => Ok(Some(UpdateVenueNameRow::from_row(&row)?)),
             None => Ok(None),
         }
     }
@@ -874,6 +1120,18 @@ impl VenueCountByCityRow {
         })
     }
 }
+/// <details><summary>SQL</summary>
+///
+/// ```sql
+/// SELECT
+///    city,
+///    count(*)
+/// FROM venue
+/// GROUP BY 1
+/// ORDER BY 1
+/// ```
+///
+/// </details>
 pub async fn venue_count_by_city(
     client: &impl deadpool_postgres::GenericClient,
 ) -> Result<
@@ -883,3 +1141,457 @@ pub async fn venue_count_by_city(
     let rows = client.query(VENUE_COUNT_BY_CITY, &[]).await?;
     Ok(rows.into_iter().map(|r| VenueCountByCityRow::from_row(&r)))
 }
+#[derive(Debug)]
+pub struct VenueCountByCity;
+/// Statements prepared once per connection
+///
+/// Prepared statements belong to the connection that prepared them, so the
+/// methods must be called with the same client that was passed to `prepare`.
+#[derive(Debug, Clone)]
+pub struct PreparedQueries {
+    list_cities: deadpool_postgres::tokio_postgres::Statement,
+    get_city: deadpool_postgres::tokio_postgres::Statement,
+    create_city: deadpool_postgres::tokio_postgres::Statement,
+    update_city_name: deadpool_postgres::tokio_postgres::Statement,
+    list_venues: deadpool_postgres::tokio_postgres::Statement,
+    delete_venue: deadpool_postgres::tokio_postgres::Statement,
+    get_venue: deadpool_postgres::tokio_postgres::Statement,
+    create_venue: deadpool_postgres::tokio_postgres::Statement,
+    update_venue_name: deadpool_postgres::tokio_postgres::Statement,
+    venue_count_by_city: deadpool_postgres::tokio_postgres::Statement,
+}
+impl PreparedQueries {
+    pub async fn prepare(
+        client: &impl deadpool_postgres::GenericClient,
+    ) -> Result<Self, deadpool_postgres::tokio_postgres::Error> {
+        Ok(Self {
+            list_cities: client.prepare_cached(LIST_CITIES).await?,
+            get_city: client
+                .prepare_typed_cached(
+                    GET_CITY,
+                    &[deadpool_postgres::tokio_postgres::types::Type::TEXT],
+                )
+                .await?,
+            create_city: client
+                .prepare_typed_cached(
+                    CREATE_CITY,
+                    &[
+                        deadpool_postgres::tokio_postgres::types::Type::TEXT,
+                        deadpool_postgres::tokio_postgres::types::Type::TEXT,
+                    ],
+                )
+                .await?,
+            update_city_name: client
+                .prepare_typed_cached(
+                    UPDATE_CITY_NAME,
+                    &[
+                        deadpool_postgres::tokio_postgres::types::Type::TEXT,
+                        deadpool_postgres::tokio_postgres::types::Type::TEXT,
+                    ],
+                )
+                .await?,
+            list_venues: client
+                .prepare_typed_cached(
+                    LIST_VENUES,
+                    &[deadpool_postgres::tokio_postgres::types::Type::TEXT],
+                )
+                .await?,
+            delete_venue: client
+                .prepare_typed_cached(
+                    DELETE_VENUE,
+                    &[deadpool_postgres::tokio_postgres::types::Type::TEXT],
+                )
+                .await?,
+            get_venue: client
+                .prepare_typed_cached(
+                    GET_VENUE,
+                    &[
+                        deadpool_postgres::tokio_postgres::types::Type::TEXT,
+                        deadpool_postgres::tokio_postgres::types::Type::TEXT,
+                    ],
+                )
+                .await?,
+            create_venue: {
+                let param_types = [
+                    deadpool_postgres::tokio_postgres::types::Type::TEXT,
+                    deadpool_postgres::tokio_postgres::types::Type::VARCHAR,
+                    deadpool_postgres::tokio_postgres::types::Type::TEXT,
+                    deadpool_postgres::tokio_postgres::types::Type::VARCHAR,
+                    lookup_type(client, "\"status\"").await?,
+                    lookup_type(client, "\"status\"[]").await?,
+                    deadpool_postgres::tokio_postgres::types::Type::TEXT_ARRAY,
+                ];
+                client
+                    .prepare_typed_cached(CREATE_VENUE, &param_types)
+                    .await?
+            },
+            update_venue_name: client
+                .prepare_typed_cached(
+                    UPDATE_VENUE_NAME,
+                    &[
+                        deadpool_postgres::tokio_postgres::types::Type::TEXT,
+                        deadpool_postgres::tokio_postgres::types::Type::VARCHAR,
+                    ],
+                )
+                .await?,
+            venue_count_by_city: client.prepare_cached(VENUE_COUNT_BY_CITY).await?,
+        })
+    }
+    pub async fn list_cities(
+        &self,
+        client: &impl deadpool_postgres::GenericClient,
+    ) -> Result<
+        impl Iterator<Item = Result<ListCitiesRow, deadpool_postgres::tokio_postgres::Error>>,
+        deadpool_postgres::tokio_postgres::Error,
+    > {
+        let rows = client.query(&self.list_cities, &[]).await?;
+        Ok(rows.into_iter().map(|row| ListCitiesRow::from_row(&row)))
+    }
+    pub async fn get_city(
+        &self,
+        client: &impl deadpool_postgres::GenericClient,
+        slug: &str,
+    ) -> Result<Option<GetCityRow>, deadpool_postgres::tokio_postgres::Error> {
+        let row = client.query_opt(&self.get_city, &[&slug]).await?;
+        match row {
+            Some(row) => Ok(Some(GetCityRow::from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+    pub async fn create_city(
+        &self,
+        client: &impl deadpool_postgres::GenericClient,
+        name: &str,
+        slug: &str,
+    ) -> Result<Option<CreateCityRow>, deadpool_postgres::tokio_postgres::Error> {
+        let row = client.query_opt(&self.create_city, &[&name, &slug]).await?;
+        match row {
+            Some(row) => Ok(Some(CreateCityRow::from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+    pub async fn update_city_name(
+        &self,
+        client: &impl deadpool_postgres::GenericClient,
+        slug: &str,
+        name: &str,
+    ) -> Result<u64, deadpool_postgres::tokio_postgres::Error> {
+        client
+            .execute(&self.update_city_name, &[&slug, &name])
+            .await
+    }
+    pub async fn list_venues(
+        &self,
+        client: &impl deadpool_postgres::GenericClient,
+        city: &str,
+    ) -> Result<
+        impl Iterator<Item = Result<ListVenuesRow, deadpool_postgres::tokio_postgres::Error>>,
+        deadpool_postgres::tokio_postgres::Error,
+    > {
+        let rows = client.query(&self.list_venues, &[&city]).await?;
+        Ok(rows.into_iter().map(|row| ListVenuesRow::from_row(&row)))
+    }
+    pub async fn delete_venue(
+        &self,
+        client: &impl deadpool_postgres::GenericClient,
+        slug: &str,
+    ) -> Result<u64, deadpool_postgres::tokio_postgres::Error> {
+        client.execute(&self.delete_venue, &[&slug]).await
+    }
+    pub async fn get_venue(
+        &self,
+        client: &impl deadpool_postgres::GenericClient,
+        slug: &str,
+        city: &str,
+    ) -> Result<Option<GetVenueRow>, deadpool_postgres::tokio_postgres::Error> {
+        let row = client.query_opt(&self.get_venue, &[&slug, &city]).await?;
+        match row {
+            Some(row) => Ok(Some(GetVenueRow::from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+    pub async fn create_venue(
+        &self,
+        client: &impl deadpool_postgres::GenericClient,
+        slug: &str,
+        name: &str,
+        city: &str,
+        spotify_playlist: &str,
+        status: Status,
+        statuses: Option<&[Status]>,
+        tags: Option<&[String]>,
+    ) -> Result<Option<CreateVenueRow>, deadpool_postgres::tokio_postgres::Error> {
+        let row = client
+            .query_opt(
+                &self.create_venue,
+                &[
+                    &slug,
+                    &name,
+                    &city,
+                    &spotify_playlist,
+                    &status,
+                    &statuses,
+                    &tags,
+                ],
+            )
+            .await?;
+        match row {
+            Some(row) => Ok(Some(CreateVenueRow::from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+    pub async fn update_venue_name(
+        &self,
+        client: &impl deadpool_postgres::GenericClient,
+        slug: &str,
+        name: &str,
+    ) -> Result<Option<UpdateVenueNameRow>, deadpool_postgres::tokio_postgres::Error> {
+        let row = client
+            .query_opt(&self.update_venue_name, &[&slug, &name])
+            .await?;
+        match row {
+            Some(row) => Ok(Some(UpdateVenueNameRow::from_row(&row)?)),
+            None => Ok(None),
+        }
+    }
+    pub async fn venue_count_by_city(
+        &self,
+        client: &impl deadpool_postgres::GenericClient,
+    ) -> Result<
+        impl Iterator<Item = Result<VenueCountByCityRow, deadpool_postgres::tokio_postgres::Error>>,
+        deadpool_postgres::tokio_postgres::Error,
+    > {
+        let rows = client.query(&self.venue_count_by_city, &[]).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| VenueCountByCityRow::from_row(&row)))
+    }
+}
+/// Statement of a generated query struct, for code generic over queries
+pub trait PgQuery {
+    /// Query name in the SQL file
+    const NAME: &'static str;
+    const SQL: &'static str;
+    /// Annotation such as `:one`
+    const ANNOTATION: &'static str;
+    /// Value decoded from each returned row, `()` for queries returning no rows
+    type Row;
+    /// Parameters in statement order, to pass to the client as `&params`
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)>;
+    fn decode_row(
+        row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error>;
+}
+impl PgQuery for ListCities {
+    const NAME: &'static str = "ListCities";
+    const SQL: &'static str = LIST_CITIES;
+    const ANNOTATION: &'static str = ":many";
+    type Row = ListCitiesRow;
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)> {
+        vec![]
+    }
+    fn decode_row(
+        row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error> {
+        ListCitiesRow::from_row(&row)
+    }
+}
+impl<'a> PgQuery for GetCity<'a> {
+    const NAME: &'static str = "GetCity";
+    const SQL: &'static str = GET_CITY;
+    const ANNOTATION: &'static str = ":one";
+    type Row = GetCityRow;
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)> {
+        vec![match &self.slug {
+            std::borrow::Cow::Borrowed(param) => param,
+            std::borrow::Cow::Owned(param) => param,
+        }]
+    }
+    fn decode_row(
+        row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error> {
+        GetCityRow::from_row(&row)
+    }
+}
+impl<'a> PgQuery for CreateCity<'a> {
+    const NAME: &'static str = "CreateCity";
+    const SQL: &'static str = CREATE_CITY;
+    const ANNOTATION: &'static str = ":one";
+    type Row = CreateCityRow;
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)> {
+        vec![
+            match &self.name {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+            match &self.slug {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+        ]
+    }
+    fn decode_row(
+        row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error> {
+        CreateCityRow::from_row(&row)
+    }
+}
+impl<'a> PgQuery for UpdateCityName<'a> {
+    const NAME: &'static str = "UpdateCityName";
+    const SQL: &'static str = UPDATE_CITY_NAME;
+    const ANNOTATION: &'static str = ":exec";
+    type Row = ();
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)> {
+        vec![
+            match &self.slug {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+            match &self.name {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+        ]
+    }
+    fn decode_row(
+        _row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error> {
+        Ok(())
+    }
+}
+impl<'a> PgQuery for ListVenues<'a> {
+    const NAME: &'static str = "ListVenues";
+    const SQL: &'static str = LIST_VENUES;
+    const ANNOTATION: &'static str = ":many";
+    type Row = ListVenuesRow;
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)> {
+        vec![match &self.city {
+            std::borrow::Cow::Borrowed(param) => param,
+            std::borrow::Cow::Owned(param) => param,
+        }]
+    }
+    fn decode_row(
+        row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error> {
+        ListVenuesRow::from_row(&row)
+    }
+}
+impl<'a> PgQuery for DeleteVenue<'a> {
+    const NAME: &'static str = "DeleteVenue";
+    const SQL: &'static str = DELETE_VENUE;
+    const ANNOTATION: &'static str = ":exec";
+    type Row = ();
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)> {
+        vec![match &self.slug {
+            std::borrow::Cow::Borrowed(param) => param,
+            std::borrow::Cow::Owned(param) => param,
+        }]
+    }
+    fn decode_row(
+        _row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error> {
+        Ok(())
+    }
+}
+impl<'a> PgQuery for GetVenue<'a> {
+    const NAME: &'static str = "GetVenue";
+    const SQL: &'static str = GET_VENUE;
+    const ANNOTATION: &'static str = ":one";
+    type Row = GetVenueRow;
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)> {
+        vec![
+            match &self.slug {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+            match &self.city {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+        ]
+    }
+    fn decode_row(
+        row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error> {
+        GetVenueRow::from_row(&row)
+    }
+}
+impl<'a> PgQuery for CreateVenue<'a> {
+    const NAME: &'static str = "CreateVenue";
+    const SQL: &'static str = CREATE_VENUE;
+    const ANNOTATION: &'static str = ":one";
+    type Row = CreateVenueRow;
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)> {
+        vec![
+            match &self.slug {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+            match &self.name {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+            match &self.city {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+            match &self.spotify_playlist {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+            &self.status,
+            match &self.statuses {
+                Some(std::borrow::Cow::Borrowed(param)) => param,
+                Some(std::borrow::Cow::Owned(param)) => param,
+                None => &None::<&[Status]>,
+            },
+            match &self.tags {
+                Some(std::borrow::Cow::Borrowed(param)) => param,
+                Some(std::borrow::Cow::Owned(param)) => param,
+                None => &None::<&[String]>,
+            },
+        ]
+    }
+    fn decode_row(
+        row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error> {
+        CreateVenueRow::from_row(&row)
+    }
+}
+impl<'a> PgQuery for UpdateVenueName<'a> {
+    const NAME: &'static str = "UpdateVenueName";
+    const SQL: &'static str = UPDATE_VENUE_NAME;
+    const ANNOTATION: &'static str = ":one";
+    type Row = UpdateVenueNameRow;
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)> {
+        vec![
+            match &self.slug {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+            match &self.name {
+                std::borrow::Cow::Borrowed(param) => param,
+                std::borrow::Cow::Owned(param) => param,
+            },
+        ]
+    }
+    fn decode_row(
+        row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error> {
+        UpdateVenueNameRow::from_row(&row)
+    }
+}
+impl PgQuery for VenueCountByCity {
+    const NAME: &'static str = "VenueCountByCity";
+    const SQL: &'static str = VENUE_COUNT_BY_CITY;
+    const ANNOTATION: &'static str = ":many";
+    type Row = VenueCountByCityRow;
+    fn params(&self) -> Vec<&(dyn deadpool_postgres::tokio_postgres::types::ToSql + Sync)> {
+        vec![]
+    }
+    fn decode_row(
+        row: &deadpool_postgres::tokio_postgres::Row,
+    ) -> Result<Self::Row, deadpool_postgres::tokio_postgres::Error> {
+        VenueCountByCityRow::from_row(&row)
+    }
+}
@@ -1,13 +1,30 @@
 use prost::Message as _;
 use quote::quote;
 use serde::Deserialize;
+use std::collections::BTreeMap;
 
 use crate::{
     Error,
     db_support::DbCrate,
+    directive::Visibility,
     plugin,
-    query::PostgresQuery,
-    user_type::{PgTypeMap, PostgresEnum, TypeMap as _},
+    query::{PostgresQuery, QueryGenOptions},
+    rust_gen::{
+        attribute_gen::{AttributeMap, ItemAttributes},
+        cursor_gen::generate_row_cursor,
+        error_gen::PostgresQueryError,
+        instrument_gen::{Instrument, generate_query_observer},
+        prepared_gen::{PostgresPreparedQueries, generate_lookup_type},
+        querier_gen::PostgresQuerierGen,
+        query_trait_gen::{generate_query_trait, generate_query_trait_impl},
+        serde_gen::{RenameAll, SerdeOptions},
+        struct_gen::{RowDecoding, generate_from_pg_row_trait},
+        timeout_gen::{check_timeout_support, parse_timeout},
+        transaction_gen::PostgresTransactionGen,
+    },
+    sqlc::QueryAnnotation,
+    user_type::{EnumGenOptions, PgTypeMap, PostgresEnum, TypeMap as _, generate_parse_enum_error},
+    utils::Renames,
 };
 
 pub fn deserialize_codegen_request(buf: &[u8]) -> crate::Result<plugin::GenerateRequest> {
@@ -19,6 +36,13 @@ pub fn serialize_codegen_response(resp: &plugin::GenerateResponse) -> Vec<u8> {
     resp.encode_to_vec()
 }
 
+/// Finds the derive of the trait `name` in a derives option, such as `std::marker::Copy`
+fn find_derive<'a>(derives: &'a [String], name: &str) -> Option<&'a String> {
+    derives
+        .iter()
+        .find(|derive| derive.rsplit("::").next().map(str::trim) == Some(name))
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
 struct CustomType {
     db_type: String,
@@ -33,6 +57,36 @@ struct PgGeneratorConfig {
     enum_derives: Vec<String>,
     row_derives: Vec<String>,
     copy_types: Vec<String>,
+    emit_interface: bool,
+    emit_mock_querier: bool,
+    mock_querier_cfg: Option<String>,
+    emit_prepared_queries: bool,
+    prepare_typed: bool,
+    emit_streaming: bool,
+    emit_cursors: bool,
+    emit_query_error: bool,
+    constraints: Vec<String>,
+    emit_transaction_helper: bool,
+    instrument: Instrument,
+    instrument_statement: bool,
+    emit_query_observer: bool,
+    query_timeout: Option<String>,
+    return_scalars: bool,
+    rename: BTreeMap<String, String>,
+    emit_enum_helpers: bool,
+    enum_unknown_variant: bool,
+    serde: bool,
+    serde_rename_all: Option<RenameAll>,
+    attributes: BTreeMap<String, ItemAttributes>,
+    visibility: Option<String>,
+    public_from_row: bool,
+    expose_query_consts: Option<bool>,
+    emit_from_row_trait: bool,
+    row_decoding: RowDecoding,
+    validate_columns: bool,
+    emit_row_refs: bool,
+    emit_owned_queries: bool,
+    emit_query_trait: bool,
 }
 
 struct PostgresGenerator {
@@ -42,6 +96,13 @@ struct PostgresGenerator {
     type_map: PgTypeMap,
     enum_derive: proc_macro2::TokenStream,
     row_derive: proc_macro2::TokenStream,
+    querier: Option<PostgresQuerierGen>,
+    prepared: Option<PostgresPreparedQueries>,
+    query_error: Option<PostgresQueryError>,
+    transaction: Option<PostgresTransactionGen>,
+    query_options: QueryGenOptions,
+    enum_options: EnumGenOptions,
+    attributes: AttributeMap,
     sqlc_version: String,
 }
 
@@ -50,17 +111,29 @@ impl PostgresGenerator {
         let config =
             serde_json::from_slice::<PgGeneratorConfig>(&req.plugin_options).unwrap_or_default();
 
-        const DEFAULT_ENUM_DERIVES: &[&str] = &[
-            "Debug",
-            "Clone",
-            "postgres_types::ToSql",
-            "postgres_types::FromSql",
-        ];
+        const DEFAULT_ENUM_DERIVES: &[&str] = &["Debug", "Clone"];
+        // enums with an `Unknown` variant implement them by hand
+        const SQL_ENUM_DERIVES: &[&str] = &["postgres_types::ToSql", "postgres_types::FromSql"];
+        // `Unknown` holds a `String`
+        if config.enum_unknown_variant {
+            if let Some(derive) = find_derive(&config.enum_derives, "Copy") {
+                return Err(Error::any_error(format!(
+                    "`enum_derives` cannot contain `{}` with `enum_unknown_variant`",
+                    derive
+                )));
+            }
+        }
+        let sql_enum_derives = if config.enum_unknown_variant {
+            &[]
+        } else {
+            SQL_ENUM_DERIVES
+        };
         let enum_derive = config
             .enum_derives
             .iter()
             .map(|s| s.as_str())
             .chain(DEFAULT_ENUM_DERIVES.iter().cloned())
+            .chain(sql_enum_derives.iter().cloned())
             .map(|s| s.parse::<proc_macro2::TokenStream>().unwrap())
             .collect::<Vec<_>>();
 
@@ -76,16 +149,71 @@ impl PostgresGenerator {
         let catalog = req
             .catalog
             .ok_or_else(|| Error::any_error("catalog not found"))?;
-        let mut pg_type_map = PgTypeMap::new(&catalog)?;
+        let mut pg_type_map = PgTypeMap::new(&catalog, Renames::new(config.rename))?;
 
+        // `MockQuerierCall` compares parameters whose types do, vouched for by the
+        // derives of the enums and of the row structs holding `overrides` types
+        if find_derive(&config.enum_derives, "PartialEq").is_some() {
+            pg_type_map.add_partial_eq_enums();
+        }
+        let row_partial_eq = find_derive(&config.row_derives, "PartialEq").is_some();
         for m in config.overrides {
             pg_type_map.add(&m.db_type, &m.rs_type)?;
+            if row_partial_eq {
+                pg_type_map.add_partial_eq_type(&m.rs_type)?;
+            }
         }
 
         for copy_type in config.copy_types {
             pg_type_map.add_copy_type(&copy_type);
         }
 
+        let visibility = match &config.visibility {
+            Some(visibility) => visibility.parse::<Visibility>()?,
+            None => Visibility::default(),
+        };
+
+        let querier = if config.emit_interface || config.emit_mock_querier {
+            Some(PostgresQuerierGen::new(
+                config.db_crate,
+                config.emit_mock_querier,
+                config.mock_querier_cfg.as_deref(),
+                visibility,
+            )?)
+        } else {
+            None
+        };
+
+        let prepared = config
+            .emit_prepared_queries
+            .then(|| PostgresPreparedQueries::new(config.db_crate, visibility));
+
+        let transaction = config.emit_transaction_helper.then(|| {
+            PostgresTransactionGen::new(config.db_crate, visibility, config.emit_query_error)
+        });
+        let query_error = if config.emit_query_error {
+            Some(PostgresQueryError::new(
+                config.db_crate,
+                visibility,
+                config.constraints,
+                pg_type_map.renames(),
+            )?)
+        } else {
+            None
+        };
+
+        let query_timeout = match &config.query_timeout {
+            Some(timeout) => parse_timeout(timeout)
+                .ok_or_else(|| Error::invalid_option("query_timeout", timeout))?,
+            None => None,
+        };
+        check_timeout_support(config.db_crate, query_timeout, "`query_timeout`")?;
+
+        let serde = SerdeOptions {
+            enabled: config.serde,
+            rename_all: config.serde_rename_all,
+        };
+
         Ok(Self {
             db_crate: config.db_crate,
             type_map: pg_type_map,
@@ -93,6 +221,37 @@ impl PostgresGenerator {
             queries: req.queries,
             enum_derive: quote! {#[derive(#(#enum_derive),*)]},
             row_derive: quote! {#[derive(#(#row_derive),*)]},
+            querier,
+            prepared,
+            query_error,
+            transaction,
+            query_options: QueryGenOptions {
+                prepare_typed: config.prepare_typed,
+                emit_streaming: config.emit_streaming,
+                emit_cursors: config.emit_cursors,
+                instrument: config.instrument,
+                instrument_statement: config.instrument_statement,
+                emit_query_observer: config.emit_query_observer,
+                timeout: query_timeout,
+                visibility,
+                return_scalars: config.return_scalars,
+                public_from_row: config.public_from_row,
+                expose_query_consts: config.expose_query_consts.unwrap_or(true),
+                emit_from_row_trait: config.emit_from_row_trait,
+                row_decoding: config.row_decoding,
+                validate_columns: config.validate_columns,
+                emit_row_refs: config.emit_row_refs,
+                emit_owned_queries: config.emit_owned_queries,
+                emit_query_trait: config.emit_query_trait,
+                serde,
+            },
+            enum_options: EnumGenOptions {
+                emit_helpers: config.emit_enum_helpers,
+                unknown_variant: config.enum_unknown_variant,
+                serde,
+                visibility,
+            },
+            attributes: AttributeMap::new(&config.attributes)?,
             sqlc_version: req.sqlc_version.clone(),
         })
     }
@@ -117,30 +276,115 @@ impl PostgresGenerator {
             .catalog
             .schemas
             .iter()
-            .flat_map(|s| s.enums.iter().map(PostgresEnum::new))
-            .collect::<Vec<_>>();
+            .flat_map(|s| s.enums.iter())
+            .map(|e| PostgresEnum::new(e, self.enum_options, self.type_map.renames()))
+            .collect::<crate::Result<Vec<_>>>()?;
 
         let pg_queries = self
             .queries
             .iter()
-            .map(|query| PostgresQuery::new(query, &self.type_map, self.db_crate))
+            .map(|query| {
+                PostgresQuery::new(
+                    query,
+                    &self.type_map,
+                    self.db_crate,
+                    self.query_options,
+                    &self.attributes,
+                )
+            })
             .collect::<crate::Result<Vec<_>>>()?;
 
+        let querier = match &self.querier {
+            Some(querier) => querier.generate(&pg_queries, &self.type_map),
+            None => quote! {},
+        };
+        let prepared = match &self.prepared {
+            Some(prepared) => prepared.generate(&pg_queries, &self.type_map),
+            None => quote! {},
+        };
+        let query_trait = if self.query_options.emit_query_trait {
+            let impls = pg_queries
+                .iter()
+                .map(|query| generate_query_trait_impl(self.db_crate, query, &self.type_map));
+            let query_trait = generate_query_trait(self.db_crate, self.query_options.visibility);
+            quote! {
+                #query_trait
+                #(#impls)*
+            }
+        } else {
+            quote! {}
+        };
+        let needs_type_lookup = (self.prepared.is_some() || self.query_options.prepare_typed)
+            && pg_queries
+                .iter()
+                .any(|q| q.query_params.needs_type_lookup());
+        let lookup_type = if needs_type_lookup {
+            generate_lookup_type(self.db_crate)
+        } else {
+            quote! {}
+        };
+        let row_cursor = if self.query_options.emit_cursors
+            && pg_queries
+                .iter()
+                .any(|q| q.query_type == QueryAnnotation::Many)
+        {
+            generate_row_cursor(self.db_crate, self.query_options.visibility)
+        } else {
+            quote! {}
+        };
+
         let pg_queries = pg_queries
             .iter()
             .map(|v| v.with_derive(&self.row_derive, &self.type_map))
             .collect::<crate::Result<Vec<_>>>()?;
         let pg_enums = pg_enums
             .iter()
-            .map(|v| v.with_derive(&self.enum_derive))
+            .map(|v| v.with_derive(&self.enum_derive, &self.attributes))
             .collect::<Vec<_>>();
+        let parse_enum_error = if self.enum_options.emit_helpers && !pg_enums.is_empty() {
+            generate_parse_enum_error(self.enum_options.visibility)
+        } else {
+            quote! {}
+        };
+
+        let from_pg_row = if self.query_options.emit_from_row_trait {
+            generate_from_pg_row_trait(self.db_crate, self.query_options.visibility)
+        } else {
+            quote! {}
+        };
+
+        let query_error = match &self.query_error {
+            Some(query_error) => query_error.generate(),
+            None => quote! {},
+        };
+
+        let transaction = match &self.transaction {
+            Some(transaction) => transaction.generate(),
+            None => quote! {},
+        };
+
+        let query_observer = if self.query_options.emit_query_observer {
+            generate_query_observer(self.db_crate, self.query_options.visibility)
+        } else {
+            quote! {}
+        };
 
         let comment = self.gen_comment()?;
 
         let tt = quote! {
             #comment
             #(#pg_enums)*
+            #parse_enum_error
+            #lookup_type
+            #row_cursor
+            #from_pg_row
+            #query_error
+            #transaction
+            #query_observer
             #(#pg_queries)*
+            #querier
+            #prepared
+            #query_trait
         };
         Ok(tt)
     }
@@ -1,5 +1,6 @@
+use crate::db_support::DbCrate;
 use crate::plugin;
-use crate::user_type::{TypeMap, col_type};
+use crate::user_type::{TypeMap, builtin_type_const, col_type};
 use proc_macro2::{Span, TokenStream};
 use quote::{ToTokens, quote};
 use std::num::NonZeroUsize;
@@ -9,10 +10,16 @@ use syn::Ident;
 #[derive(Debug, Clone)]
 pub(crate) struct PgColumn {
     pub(crate) name: String,
+    /// Column name in the result set
+    pub(crate) column_name: String,
+    /// Database type name such as `pg_catalog.int4`
+    pub(crate) pg_type: String,
     pub(crate) rs_type: TokenStream,
     /// None => not array
     pub(crate) array_dim: Option<NonZeroUsize>,
     pub(crate) is_nullable: bool,
+    /// Column comment from the catalog
+    pub(crate) comment: String,
 }
 
 impl PgColumn {
@@ -34,16 +41,57 @@ impl PgColumn {
 
         Ok(Self {
             name: col_name,
+            column_name: column.name.clone(),
+            pg_type: col_type,
             rs_type,
             array_dim,
             is_nullable,
+            comment: column.comment.clone(),
         })
     }
 }
 
-impl ToTokens for PgColumn {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let field_ident = Ident::new(&self.name, Span::call_site());
+impl PgColumn {
+    /// Returns `postgres_types::Type` tokens for built-in database types
+    pub(crate) fn pg_type_tokens(&self, db_crate: DbCrate) -> Option<TokenStream> {
+        let type_const = builtin_type_const(&self.pg_type)?;
+        let type_const = match self.array_dim {
+            Some(_) => format!("{}_ARRAY", type_const),
+            None => type_const.to_string(),
+        };
+        let type_ident = Ident::new(&type_const, Span::call_site());
+        let type_path = db_crate.type_ident();
+        Some(quote! { #type_path::#type_ident })
+    }
+
+    /// Returns an expression evaluating to the column's `Type`
+    ///
+    /// Types that are not built-in, such as enums and composites, are looked up
+    /// on the server with the generated `lookup_type` helper.
+    pub(crate) fn pg_type_expr(&self, db_crate: DbCrate) -> TokenStream {
+        if let Some(type_tokens) = self.pg_type_tokens(db_crate) {
+            return type_tokens;
+        }
+        let mut type_name = self
+            .pg_type
+            .split('.')
+            .map(|part| format!("\"{}\"", part))
+            .collect::<Vec<_>>()
+            .join(".");
+        if self.array_dim.is_some() {
+            type_name.push_str("[]");
+        }
+        let await_def = db_crate.await_ident();
+        quote! { lookup_type(client, #type_name)#await_def? }
+    }
+
+    /// Whether the column type must be looked up on the server
+    pub(crate) fn needs_type_lookup(&self) -> bool {
+        builtin_type_const(&self.pg_type).is_none()
+    }
+
+    /// Rust type of the column wrapped in `Vec` for arrays and `Option` when nullable
+    pub(crate) fn field_type(&self) -> TokenStream {
         let rs_type = &self.rs_type;
         let mut ty_tokens = quote! { #rs_type };
 
@@ -57,7 +105,40 @@ impl ToTokens for PgColumn {
             ty_tokens = quote! { Option<#ty_tokens> };
         }
 
+        ty_tokens
+    }
+
+    /// Field type borrowing text and `bytea` values from a row living for `'r`
+    ///
+    /// `None` when the column is decoded into an owned value.
+    pub(crate) fn borrowed_field_type(&self) -> Option<TokenStream> {
+        if self.array_dim.is_some() {
+            return None;
+        }
+        let borrowed = match PgColumnRef::new(self.clone())
+            .wrap_type()
+            .to_string()
+            .as_str()
+        {
+            "str" => quote! { &'r str },
+            "Vec < u8 >" => quote! { &'r [u8] },
+            _ => return None,
+        };
+        if self.is_nullable {
+            Some(quote! { Option<#borrowed> })
+        } else {
+            Some(borrowed)
+        }
+    }
+}
+
+impl ToTokens for PgColumn {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let field_ident = crate::utils::rust_ident(&self.name);
+        let ty_tokens = self.field_type();
+        let docs = crate::utils::doc_attrs(self.comment.lines());
         tokens.extend(quote! {
+            #docs
             pub #field_ident: #ty_tokens
         });
     }
@@ -118,7 +199,7 @@ impl PgColumnRef {
         &self,
         type_map: &impl crate::user_type::TypeMap,
     ) -> proc_macro2::TokenStream {
-        let field_ident = Ident::new(&self.inner.name, Span::call_site());
+        let field_ident = crate::utils::rust_ident(&self.inner.name);
         let rs_type = self.wrap_type();
 
         let param_type = if self.is_copy_cheap_type(type_map) {
@@ -145,7 +226,7 @@ impl PgColumnRef {
 
 impl ToTokens for PgColumnRef {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let field_ident = Ident::new(&self.inner.name, Span::call_site());
+        let field_ident = crate::utils::rust_ident(&self.inner.name);
         let rs_type = self.wrap_type();
 
         // Fallback to reference for backward compatibility when type map not available
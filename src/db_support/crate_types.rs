@@ -29,15 +29,40 @@ impl<'de> Deserialize<'de> for DbCrate {
 impl DbCrate {
     /// Returns the client type tokens for the specific database crate
     pub(crate) fn client_ident(&self) -> TokenStream {
+        let generic_client = self.generic_client_ident();
+        match self {
+            DbCrate::TokioPostgres | DbCrate::DeadPoolPostgres => {
+                quote! {&impl #generic_client}
+            }
+            DbCrate::Postgres => {
+                quote! {&mut impl #generic_client}
+            }
+        }
+    }
+
+    /// Returns the `GenericClient` trait path for the specific database crate
+    pub(crate) fn generic_client_ident(&self) -> TokenStream {
         match self {
             DbCrate::TokioPostgres => {
-                quote! {&impl tokio_postgres::GenericClient}
+                quote! {tokio_postgres::GenericClient}
             }
             DbCrate::Postgres => {
-                quote! {&mut impl postgres::GenericClient}
+                quote! {postgres::GenericClient}
             }
             DbCrate::DeadPoolPostgres => {
-                quote! {&impl deadpool_postgres::GenericClient}
+                quote! {deadpool_postgres::GenericClient}
+            }
+        }
+    }
+
+    /// Returns `&self` or `&mut self` matching how the crate borrows its client
+    pub(crate) fn receiver_ident(&self) -> TokenStream {
+        match self {
+            Self::TokioPostgres | Self::DeadPoolPostgres => {
+                quote! {&self}
+            }
+            Self::Postgres => {
+                quote! {&mut self}
             }
         }
     }
@@ -83,6 +108,141 @@ impl DbCrate {
         }
     }
 
+    /// Returns the prepared statement type tokens for the specific database crate
+    pub(crate) fn statement_ident(&self) -> TokenStream {
+        match self {
+            DbCrate::TokioPostgres => {
+                quote! {tokio_postgres::Statement}
+            }
+            DbCrate::Postgres => {
+                quote! {postgres::Statement}
+            }
+            DbCrate::DeadPoolPostgres => {
+                quote! {deadpool_postgres::tokio_postgres::Statement}
+            }
+        }
+    }
+
+    /// Returns the `Type` tokens used to describe statement parameters
+    pub(crate) fn type_ident(&self) -> TokenStream {
+        match self {
+            DbCrate::TokioPostgres => {
+                quote! {tokio_postgres::types::Type}
+            }
+            DbCrate::Postgres => {
+                quote! {postgres::types::Type}
+            }
+            DbCrate::DeadPoolPostgres => {
+                quote! {deadpool_postgres::tokio_postgres::types::Type}
+            }
+        }
+    }
+
+    /// Returns the transaction type tokens, without lifetime, for the specific database crate
+    pub(crate) fn transaction_ident(&self) -> TokenStream {
+        match self {
+            DbCrate::TokioPostgres => {
+                quote! {tokio_postgres::Transaction}
+            }
+            DbCrate::Postgres => {
+                quote! {postgres::Transaction}
+            }
+            DbCrate::DeadPoolPostgres => {
+                quote! {deadpool_postgres::Transaction}
+            }
+        }
+    }
+
+    /// Returns the concrete connection type tokens for the specific database crate
+    pub(crate) fn connection_ident(&self) -> TokenStream {
+        match self {
+            DbCrate::TokioPostgres => {
+                quote! {tokio_postgres::Client}
+            }
+            DbCrate::Postgres => {
+                quote! {postgres::Client}
+            }
+            DbCrate::DeadPoolPostgres => {
+                quote! {deadpool_postgres::Client}
+            }
+        }
+    }
+
+    /// Returns the `IsolationLevel` tokens for the specific database crate
+    pub(crate) fn isolation_level_ident(&self) -> TokenStream {
+        match self {
+            DbCrate::TokioPostgres => {
+                quote! {tokio_postgres::IsolationLevel}
+            }
+            DbCrate::Postgres => {
+                quote! {postgres::IsolationLevel}
+            }
+            DbCrate::DeadPoolPostgres => {
+                quote! {deadpool_postgres::tokio_postgres::IsolationLevel}
+            }
+        }
+    }
+
+    /// Returns the portal type tokens for the specific database crate
+    pub(crate) fn portal_ident(&self) -> TokenStream {
+        match self {
+            DbCrate::TokioPostgres => {
+                quote! {tokio_postgres::Portal}
+            }
+            DbCrate::Postgres => {
+                quote! {postgres::Portal}
+            }
+            DbCrate::DeadPoolPostgres => {
+                quote! {deadpool_postgres::tokio_postgres::Portal}
+            }
+        }
+    }
+
+    /// Returns the `SqlState` tokens for the specific database crate
+    pub(crate) fn sqlstate_ident(&self) -> TokenStream {
+        match self {
+            DbCrate::TokioPostgres => {
+                quote! {tokio_postgres::error::SqlState}
+            }
+            DbCrate::Postgres => {
+                quote! {postgres::error::SqlState}
+            }
+            DbCrate::DeadPoolPostgres => {
+                quote! {deadpool_postgres::tokio_postgres::error::SqlState}
+            }
+        }
+    }
+
+    /// Returns the `ToSql` trait tokens for the specific database crate
+    pub(crate) fn tosql_ident(&self) -> TokenStream {
+        match self {
+            DbCrate::TokioPostgres => {
+                quote! {tokio_postgres::types::ToSql}
+            }
+            DbCrate::Postgres => {
+                quote! {postgres::types::ToSql}
+            }
+            DbCrate::DeadPoolPostgres => {
+                quote! {deadpool_postgres::tokio_postgres::types::ToSql}
+            }
+        }
+    }
+
+    /// Returns the `FromSql` trait tokens for the specific database crate
+    pub(crate) fn fromsql_ident(&self) -> TokenStream {
+        match self {
+            DbCrate::TokioPostgres => {
+                quote! {tokio_postgres::types::FromSql}
+            }
+            DbCrate::Postgres => {
+                quote! {postgres::types::FromSql}
+            }
+            DbCrate::DeadPoolPostgres => {
+                quote! {deadpool_postgres::tokio_postgres::types::FromSql}
+            }
+        }
+    }
+
     /// Returns the row type tokens for the specific database crate
     pub(crate) fn row_ident(&self) -> TokenStream {
         match self {
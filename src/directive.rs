@@ -0,0 +1,278 @@
+use crate::error::Error;
+use crate::plugin;
+use crate::rust_gen::timeout_gen::parse_timeout;
+use proc_macro2::TokenStream;
+use quote::{ToTokens, quote};
+
+const RUST_DIRECTIVE_PREFIX: &str = "@rust.";
+const TIMEOUT_DIRECTIVE: &str = "@timeout";
+
+/// Whether a query comment line is a directive rather than documentation
+pub(crate) fn is_directive(comment: &str) -> bool {
+    comment.trim_start().starts_with('@')
+}
+
+fn unknown_directive(directive: &str, query_name: &str) -> Error {
+    Error::any_error(format!(
+        "unknown directive `{}` in query {}",
+        directive, query_name
+    ))
+}
+
+/// Visibility of the items generated for a query, ordered from the widest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub(crate) enum Visibility {
+    #[default]
+    Public,
+    Crate,
+    Super,
+}
+
+impl Visibility {
+    /// The narrower of the two visibilities
+    pub(crate) fn narrowest(self, other: Self) -> Self {
+        self.max(other)
+    }
+}
+
+impl std::str::FromStr for Visibility {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized = s.split_whitespace().collect::<String>();
+        match normalized.as_str() {
+            "pub" => Ok(Visibility::Public),
+            "pub(crate)" => Ok(Visibility::Crate),
+            "pub(super)" => Ok(Visibility::Super),
+            _ => Err(Error::invalid_option("visibility", s)),
+        }
+    }
+}
+
+impl ToTokens for Visibility {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(match self {
+            Visibility::Public => quote! { pub },
+            Visibility::Crate => quote! { pub(crate) },
+            Visibility::Super => quote! { pub(super) },
+        });
+    }
+}
+
+/// Per-query options written as `-- @rust.xxx(...)` comments after the `-- name:` line
+///
+/// ```sql
+/// -- name: GetAuthor :one
+/// -- @rust.derive(serde::Serialize)
+/// -- @rust.return(row)
+/// -- @rust.visibility(pub(crate))
+/// -- @rust.deprecated("use GetAuthorById")
+/// -- @timeout 500ms
+/// SELECT * FROM authors WHERE id = $1;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct QueryDirectives {
+    /// Extra derives of the row struct
+    pub(crate) derives: Vec<String>,
+    pub(crate) visibility: Option<Visibility>,
+    /// `Some` with an optional note when the query is deprecated
+    pub(crate) deprecated: Option<Option<String>>,
+    /// `Some(None)` disables the global timeout
+    pub(crate) timeout: Option<Option<u64>>,
+    /// `Some(true)` returns the single column instead of a row struct
+    pub(crate) scalar: Option<bool>,
+}
+
+impl QueryDirectives {
+    pub(crate) fn new(query: &plugin::Query) -> crate::Result<Self> {
+        let mut directives = Self::default();
+        for comment in &query.comments {
+            let comment = comment.trim();
+            if let Some(value) = comment.strip_prefix(TIMEOUT_DIRECTIVE) {
+                // `@timeoutX` is a typo rather than a directive of another tool
+                if !value.is_empty() && !value.starts_with(char::is_whitespace) {
+                    return Err(unknown_directive(comment, &query.name));
+                }
+                let timeout = parse_timeout(value)
+                    .ok_or_else(|| Error::invalid_option(TIMEOUT_DIRECTIVE, value.trim()))?;
+                directives.timeout = Some(timeout);
+            } else if let Some(directive) = comment.strip_prefix(RUST_DIRECTIVE_PREFIX) {
+                directives.apply(&query.name, directive)?;
+            } else if comment.starts_with(RUST_DIRECTIVE_PREFIX.trim_end_matches('.')) {
+                return Err(unknown_directive(comment, &query.name));
+            }
+        }
+        Ok(directives)
+    }
+
+    /// Applies one `name(args)` directive
+    fn apply(&mut self, query_name: &str, directive: &str) -> crate::Result<()> {
+        let (name, args) = match directive.split_once('(') {
+            Some((name, args)) => {
+                let args = args.trim_end().strip_suffix(')').ok_or_else(|| {
+                    Error::invalid_option(&format!("@rust.{}", name.trim()), args)
+                })?;
+                (name.trim(), Some(args.trim()))
+            }
+            None => (directive.trim(), None),
+        };
+        let option = format!("{}{}", RUST_DIRECTIVE_PREFIX, name);
+
+        match (name, args) {
+            ("derive", Some(args)) => {
+                for derive in args.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                    syn::parse_str::<syn::Path>(derive)
+                        .map_err(|_| Error::invalid_option(&option, derive))?;
+                    self.derives.push(derive.to_string());
+                }
+            }
+            ("visibility", Some(args)) => {
+                self.visibility = Some(
+                    args.parse()
+                        .map_err(|_| Error::invalid_option(&option, args))?,
+                );
+            }
+            ("return", Some("scalar")) => self.scalar = Some(true),
+            ("return", Some("row")) => self.scalar = Some(false),
+            ("return", Some(args)) => return Err(Error::invalid_option(&option, args)),
+            ("deprecated", None) => self.deprecated = Some(None),
+            ("deprecated", Some(args)) => {
+                let note = syn::parse_str::<syn::LitStr>(args)
+                    .map_err(|_| Error::invalid_option(&option, args))?;
+                self.deprecated = Some(Some(note.value()));
+            }
+            _ => {
+                return Err(unknown_directive(
+                    &format!("{}{}", RUST_DIRECTIVE_PREFIX, directive.trim()),
+                    query_name,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Extra `#[derive(...)]` of the row struct
+    pub(crate) fn row_derive(&self) -> TokenStream {
+        if self.derives.is_empty() {
+            return quote! {};
+        }
+        let derives = self
+            .derives
+            .iter()
+            .map(|s| s.parse::<TokenStream>().unwrap());
+        quote! { #[derive(#(#derives),*)] }
+    }
+
+    /// `#[deprecated]` attribute of the query functions
+    pub(crate) fn deprecated_attr(&self) -> TokenStream {
+        match &self.deprecated {
+            Some(Some(note)) => quote! { #[deprecated = #note] },
+            Some(None) => quote! { #[deprecated] },
+            None => quote! {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(comments: &[&str]) -> crate::Result<QueryDirectives> {
+        let query = plugin::Query {
+            name: "GetAuthor".to_string(),
+            comments: comments.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        };
+        QueryDirectives::new(&query)
+    }
+
+    #[test]
+    fn directives_are_told_from_docs() {
+        assert!(is_directive("@rust.return(row)"));
+        assert!(is_directive("  @timeout 1s"));
+        assert!(!is_directive("Returns the author"));
+        assert!(!is_directive("mail me @ work"));
+    }
+
+    #[test]
+    fn visibility_from_str() {
+        assert_eq!("pub".parse::<Visibility>().unwrap(), Visibility::Public);
+        assert_eq!(
+            "pub( crate )".parse::<Visibility>().unwrap(),
+            Visibility::Crate
+        );
+        assert_eq!(
+            "pub(super)".parse::<Visibility>().unwrap(),
+            Visibility::Super
+        );
+        assert!("pub(self)".parse::<Visibility>().is_err());
+        assert!("private".parse::<Visibility>().is_err());
+    }
+
+    #[test]
+    fn narrowest_visibility() {
+        assert_eq!(
+            Visibility::Public.narrowest(Visibility::Crate),
+            Visibility::Crate
+        );
+        assert_eq!(
+            Visibility::Super.narrowest(Visibility::Crate),
+            Visibility::Super
+        );
+        assert_eq!(
+            Visibility::Public.narrowest(Visibility::Public),
+            Visibility::Public
+        );
+    }
+
+    #[test]
+    fn valid_directives() {
+        let directives = parse(&[
+            "Returns the author",
+            "@rust.derive(serde::Serialize, Hash)",
+            "@rust.visibility(pub(crate))",
+            "@rust.return(scalar)",
+            "@rust.deprecated(\"use GetAuthorById\")",
+            "@timeout 2s",
+        ])
+        .unwrap();
+        assert_eq!(
+            directives,
+            QueryDirectives {
+                derives: vec!["serde::Serialize".to_string(), "Hash".to_string()],
+                visibility: Some(Visibility::Crate),
+                deprecated: Some(Some("use GetAuthorById".to_string())),
+                timeout: Some(Some(2_000)),
+                scalar: Some(true),
+            }
+        );
+
+        let directives = parse(&["@rust.deprecated", "@rust.return(row)", "@timeout 0"]).unwrap();
+        assert_eq!(directives.deprecated, Some(None));
+        assert_eq!(directives.scalar, Some(false));
+        assert_eq!(directives.timeout, Some(None));
+    }
+
+    #[test]
+    fn invalid_directive_args() {
+        assert!(parse(&["@rust.derive(not a path)"]).is_err());
+        assert!(parse(&["@rust.visibility(private)"]).is_err());
+        assert!(parse(&["@rust.return(tuple)"]).is_err());
+        assert!(parse(&["@rust.deprecated(unquoted)"]).is_err());
+        assert!(parse(&["@rust.derive(Hash"]).is_err());
+        assert!(parse(&["@timeout 5 hours"]).is_err());
+        assert!(parse(&["@timeout soon"]).is_err());
+    }
+
+    #[test]
+    fn unknown_directives() {
+        assert!(parse(&["@rust.derives(Hash)"]).is_err());
+        assert!(parse(&["@rust.derivefoo"]).is_err());
+        assert!(parse(&["@rust.return"]).is_err());
+        assert!(parse(&["@rustderive(Hash)"]).is_err());
+        assert!(parse(&["@timeoutX 5s"]).is_err());
+        assert!(parse(&["@timeout5s"]).is_err());
+        // directives of other tools are left alone
+        assert!(parse(&["@sqlc-vet-disable"]).is_ok());
+    }
+}
@@ -9,6 +9,7 @@ pub enum Error {
     InvalidRustType(String),
     MissingColInfo(String),
     UnSupportedAnnotation(String),
+    InvalidOption(String),
     AnyError(String),
     Decode(prost::DecodeError),
     BackTrace {
@@ -45,6 +46,10 @@ impl Error {
         Self::UnSupportedAnnotation(annotation.to_string()).into_backtrace()
     }
 
+    pub(crate) fn invalid_option<S: Display>(option: &str, value: S) -> Self {
+        Self::InvalidOption(format!("`{}` = `{}`", option, value)).into_backtrace()
+    }
+
     pub(crate) fn any_error<S: Display>(message: S) -> Self {
         Self::AnyError(message.to_string()).into_backtrace()
     }
@@ -65,6 +70,9 @@ impl Display for Error {
             Error::UnSupportedAnnotation(annotation) => {
                 write!(f, "query annotation `{}` is not supported", annotation)
             }
+            Error::InvalidOption(option) => {
+                write!(f, "invalid plugin option {}", option)
+            }
             Error::Decode(e) => e.fmt(f),
             Error::AnyError(message) => {
                 const ISSUE_URL: &str =
@@ -1,24 +1,73 @@
+use crate::Error;
 use crate::db_support::DbCrate;
+use crate::directive::{QueryDirectives, Visibility};
 use crate::plugin;
+use crate::rust_gen::attribute_gen::AttributeMap;
 use crate::rust_gen::builder_gen::PostgresBuilderGen;
 use crate::rust_gen::const_gen::PostgresConstQuery;
 use crate::rust_gen::func_gen::PostgresFunc;
+use crate::rust_gen::instrument_gen::Instrument;
 use crate::rust_gen::param_gen::PgParams;
+use crate::rust_gen::serde_gen::SerdeOptions;
 use crate::rust_gen::struct_api_gen::PostgresStructApi;
-use crate::rust_gen::struct_gen::PgStruct;
+use crate::rust_gen::struct_gen::{PgStruct, RowDecoding};
+use crate::rust_gen::timeout_gen::check_timeout_support;
 use crate::sqlc::QueryAnnotation;
 use crate::user_type::TypeMap;
 use quote::quote;
 
+/// Generator options shared by every query
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct QueryGenOptions {
+    /// Prepare statements with explicit parameter types
+    pub(crate) prepare_typed: bool,
+    /// Emit streaming variants of `:many` queries
+    pub(crate) emit_streaming: bool,
+    /// Emit portal-based cursor variants of `:many` queries
+    pub(crate) emit_cursors: bool,
+    /// Instrumentation wrapped around query functions
+    pub(crate) instrument: Instrument,
+    /// Record the SQL text as `db.statement`
+    pub(crate) instrument_statement: bool,
+    /// Report queries to the global `QueryObserver`
+    pub(crate) emit_query_observer: bool,
+    /// Query timeout in milliseconds
+    pub(crate) timeout: Option<u64>,
+    /// Visibility of the query functions, constant, struct and builder
+    pub(crate) visibility: Visibility,
+    /// Return the column directly from single-column queries
+    pub(crate) return_scalars: bool,
+    /// Make `from_row` of row structs `pub` instead of `pub(crate)`
+    pub(crate) public_from_row: bool,
+    /// Give the SQL constants the query visibility instead of keeping them private
+    pub(crate) expose_query_consts: bool,
+    /// Implement the `FromPgRow` trait for row structs
+    pub(crate) emit_from_row_trait: bool,
+    /// Read row columns by position or by name
+    pub(crate) row_decoding: RowDecoding,
+    /// Debug assertions on the result columns in `from_row`
+    pub(crate) validate_columns: bool,
+    /// Borrowed `XxxRowRef<'r>` variants of row structs
+    pub(crate) emit_row_refs: bool,
+    /// `Clone` and `into_owned` on query structs
+    pub(crate) emit_owned_queries: bool,
+    /// `PgQuery` trait implemented by query structs
+    pub(crate) emit_query_trait: bool,
+    /// `serde` derives of row and query structs
+    pub(crate) serde: SerdeOptions,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct PostgresQuery {
-    query_type: QueryAnnotation,
-    query_const: PostgresConstQuery,
-    returning_row: PgStruct,
-    query_params: PgParams,
-    query_func: PostgresFunc,
-    struct_api: PostgresStructApi,
-    builder_gen: PostgresBuilderGen,
+    pub(crate) query_type: QueryAnnotation,
+    pub(crate) query_const: PostgresConstQuery,
+    pub(crate) returning_row: PgStruct,
+    pub(crate) query_params: PgParams,
+    pub(crate) query_func: PostgresFunc,
+    pub(crate) struct_api: PostgresStructApi,
+    pub(crate) builder_gen: PostgresBuilderGen,
+    /// Extra derives of the row struct
+    pub(crate) row_attrs: proc_macro2::TokenStream,
 }
 
 impl PostgresQuery {
@@ -26,15 +75,75 @@ impl PostgresQuery {
         query: &plugin::Query,
         pg_map: &impl TypeMap,
         db_crate: DbCrate,
+        options: QueryGenOptions,
+        attributes: &AttributeMap,
     ) -> crate::Result<Self> {
         let query_type = query.cmd.parse::<QueryAnnotation>().unwrap();
+        let directives = QueryDirectives::new(query)?;
+        if let Some(timeout) = directives.timeout {
+            check_timeout_support(
+                db_crate,
+                timeout,
+                &format!("`@timeout` in query {}", query.name),
+            )?;
+        }
+        // rows appear in the `Querier` and `PreparedQueries` signatures, so they
+        // keep the global visibility, which the directive cannot widen
+        let row_options = options;
+        let options = QueryGenOptions {
+            timeout: directives.timeout.unwrap_or(options.timeout),
+            visibility: directives
+                .visibility
+                .map_or(options.visibility, |v| v.narrowest(options.visibility)),
+            ..options
+        };
+        let fn_attrs = directives.deprecated_attr();
 
-        let query_const = PostgresConstQuery::new(query, &query_type);
-        let returning_row = PgStruct::new(query, pg_map, db_crate)?;
+        let query_const = PostgresConstQuery::new(
+            query,
+            &query_type,
+            options.expose_query_consts.then_some(options.visibility),
+            pg_map.renames(),
+        );
+        let scalar = match directives.scalar {
+            Some(true) if query.columns.len() != 1 => {
+                return Err(Error::any_error(format!(
+                    "`@rust.return(scalar)` requires a single column in query {}",
+                    query.name
+                )));
+            }
+            Some(scalar) => scalar,
+            None => options.return_scalars && query.columns.len() == 1,
+        };
+        // scalar and `:exec` queries have no row struct to derive on
+        if !directives.derives.is_empty() && (scalar || query_type == QueryAnnotation::Exec) {
+            return Err(Error::any_error(format!(
+                "`@rust.derive` requires a row struct, which query {} does not return",
+                query.name
+            )));
+        }
+        let returning_row = PgStruct::new(query, pg_map, db_crate, scalar, row_options)?;
         let query_params = PgParams::new(query, pg_map)?;
-        let query_func = PostgresFunc::new(query, query_type.clone(), db_crate);
-        let struct_api = PostgresStructApi::new(query, query_type.clone(), db_crate);
-        let builder_gen = PostgresBuilderGen::new(crate::utils::rust_value_ident(&query.name));
+        let query_func = PostgresFunc::new(
+            query,
+            query_type.clone(),
+            db_crate,
+            options,
+            fn_attrs,
+            pg_map.renames(),
+        );
+        let rust_name = crate::utils::rust_value_ident(&query.name, pg_map.renames());
+        let struct_api = PostgresStructApi::new(
+            query,
+            query_type.clone(),
+            db_crate,
+            options,
+            attributes.query_attrs(&query.name, &rust_name),
+            pg_map.renames(),
+        );
+        let row_attrs = attributes.row_attrs(&query.name, &rust_name);
+        let builder_gen =
+            PostgresBuilderGen::new(rust_name, options.visibility, options.emit_owned_queries);
         Ok(Self {
             query_type,
             query_const,
@@ -43,6 +152,11 @@ impl PostgresQuery {
             query_func,
             struct_api,
             builder_gen,
+            row_attrs: {
+                let row_derive = directives.row_derive();
+                let serde_attrs = options.serde.struct_attrs();
+                quote! { #row_derive #serde_attrs #row_attrs }
+            },
         })
     }
 
@@ -59,6 +173,7 @@ impl PostgresQuery {
             query_func,
             struct_api,
             builder_gen,
+            row_attrs,
         } = self;
         // Generate struct-based API only if there are parameters
         let struct_api_tokens = if !query_params.params.is_empty() {
@@ -79,7 +194,7 @@ impl PostgresQuery {
                 #builder_pattern
             }
         } else {
-            quote! {}
+            struct_api.generate_unit_query_struct()
         };
 
         let query_func = query_func.generate(query_const, returning_row, query_params, type_map)?;
@@ -88,6 +203,13 @@ impl PostgresQuery {
         let query_tt = query_const.to_tokens()?;
 
         let tokens = match query_type {
+            _ if returning_row.scalar => {
+                quote! {
+                    #query_tt
+                    #query_func
+                    #struct_api_tokens
+                }
+            }
             QueryAnnotation::Exec => {
                 quote! {
                     #query_tt
@@ -96,9 +218,12 @@ impl PostgresQuery {
                 }
             }
             _ => {
+                let row_docs = returning_row.doc_attrs();
                 quote! {
                     #query_tt
+                    #row_docs
                     #row_derive
+                    #row_attrs
                     #returning_row
                     #query_func
                     #struct_api_tokens
@@ -109,3 +234,62 @@ impl PostgresQuery {
         Ok(tokens)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user_type::PgTypeMap;
+    use crate::utils::Renames;
+
+    fn column(name: &str) -> plugin::Column {
+        plugin::Column {
+            name: name.to_string(),
+            not_null: true,
+            r#type: Some(plugin::Identifier {
+                name: "int4".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn new_query(cmd: &str, columns: &[&str], comments: &[&str]) -> crate::Result<PostgresQuery> {
+        let query = plugin::Query {
+            name: "GetCount".to_string(),
+            cmd: cmd.to_string(),
+            columns: columns.iter().map(|name| column(name)).collect(),
+            comments: comments.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        };
+        let type_map = PgTypeMap::new(&plugin::Catalog::default(), Renames::default())?;
+        PostgresQuery::new(
+            &query,
+            &type_map,
+            DbCrate::TokioPostgres,
+            QueryGenOptions::default(),
+            &AttributeMap::default(),
+        )
+    }
+
+    #[test]
+    fn derive_applies_to_row_structs() {
+        assert!(new_query(":one", &["a", "b"], &["@rust.derive(Hash)"]).is_ok());
+    }
+
+    #[test]
+    fn derive_without_row_struct() {
+        let scalar = new_query(
+            ":one",
+            &["a"],
+            &["@rust.return(scalar)", "@rust.derive(Hash)"],
+        );
+        assert!(scalar.is_err());
+        assert!(new_query(":exec", &[], &["@rust.derive(Hash)"]).is_err());
+    }
+
+    #[test]
+    fn scalar_requires_single_column() {
+        assert!(new_query(":one", &["a", "b"], &["@rust.return(scalar)"]).is_err());
+        assert!(new_query(":one", &["a"], &["@rust.return(scalar)"]).is_ok());
+    }
+}
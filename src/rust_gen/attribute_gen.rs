@@ -0,0 +1,186 @@
+use crate::error::Error;
+use proc_macro2::TokenStream;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use syn::parse::Parser;
+
+/// Attributes added to the items generated for matching names, written like `#[derive(Hash)]`
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(default)]
+pub struct ItemAttributes {
+    /// Row struct of a query
+    row: Vec<String>,
+    /// Query struct holding the parameters
+    query: Vec<String>,
+    /// Enum
+    #[serde(rename = "enum")]
+    enum_attrs: Vec<String>,
+}
+
+/// Parsed `attributes` option keyed by query or enum name patterns
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AttributeMap {
+    rules: Vec<(String, ParsedAttributes)>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ParsedAttributes {
+    row: TokenStream,
+    query: TokenStream,
+    enum_attrs: TokenStream,
+}
+
+impl AttributeMap {
+    pub(crate) fn new(config: &BTreeMap<String, ItemAttributes>) -> crate::Result<Self> {
+        let rules = config
+            .iter()
+            .map(|(pattern, attributes)| {
+                let parsed = ParsedAttributes {
+                    row: parse_attributes(&attributes.row)?,
+                    query: parse_attributes(&attributes.query)?,
+                    enum_attrs: parse_attributes(&attributes.enum_attrs)?,
+                };
+                Ok((pattern.clone(), parsed))
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Self { rules })
+    }
+
+    fn matching<'a>(&'a self, names: &'a [&str]) -> impl Iterator<Item = &'a ParsedAttributes> {
+        self.rules
+            .iter()
+            .filter(|(pattern, _)| names.iter().any(|name| glob_match(pattern, name)))
+            .map(|(_, attributes)| attributes)
+    }
+
+    /// Attributes of the row struct of the query named `db_name` in the SQL file and
+    /// `rust_name` in the code
+    pub(crate) fn row_attrs(&self, db_name: &str, rust_name: &str) -> TokenStream {
+        self.matching(&[db_name, rust_name])
+            .map(|a| a.row.clone())
+            .collect()
+    }
+
+    /// Attributes of the query struct of the query named `db_name` in the SQL file and
+    /// `rust_name` in the code
+    pub(crate) fn query_attrs(&self, db_name: &str, rust_name: &str) -> TokenStream {
+        self.matching(&[db_name, rust_name])
+            .map(|a| a.query.clone())
+            .collect()
+    }
+
+    /// Attributes of the enum named `db_name` in the catalog and `rust_name` in the code
+    pub(crate) fn enum_attrs(&self, db_name: &str, rust_name: &str) -> TokenStream {
+        self.matching(&[db_name, rust_name])
+            .map(|a| a.enum_attrs.clone())
+            .collect()
+    }
+}
+
+fn parse_attributes(attributes: &[String]) -> crate::Result<TokenStream> {
+    attributes
+        .iter()
+        .map(|attribute| {
+            let tokens = attribute
+                .parse::<TokenStream>()
+                .map_err(|_| Error::invalid_option("attributes", attribute))?;
+            syn::Attribute::parse_outer
+                .parse2(tokens.clone())
+                .map_err(|_| Error::invalid_option("attributes", attribute))?;
+            Ok(tokens)
+        })
+        .collect()
+}
+
+/// Matches `name` against a pattern where `*` matches any characters and `?` one character
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    // Position of the last `*` and the name position it was tried at
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    n = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_star() {
+        assert!(glob_match("*", ""));
+        assert!(glob_match("*", "GetAuthor"));
+        assert!(glob_match("Get*Row", "GetAuthorRow"));
+        assert!(glob_match("Get*Row", "GetRow"));
+        assert!(glob_match("*Author*", "ListAuthorsByName"));
+        assert!(glob_match("**", "GetAuthor"));
+    }
+
+    #[test]
+    fn glob_question_mark() {
+        assert!(glob_match("GetAuthor?", "GetAuthors"));
+        assert!(glob_match("?etAuthor", "GetAuthor"));
+        assert!(!glob_match("GetAuthor?", "GetAuthor"));
+        assert!(!glob_match("Get?", "GetAuthor"));
+    }
+
+    #[test]
+    fn glob_trailing_star() {
+        assert!(glob_match("Get*", "Get"));
+        assert!(glob_match("Get*", "GetAuthor"));
+        assert!(glob_match("Get?*", "GetAuthor"));
+        assert!(!glob_match("Get?*", "Get"));
+    }
+
+    #[test]
+    fn glob_non_matches() {
+        assert!(!glob_match("GetAuthor", "GetAuthors"));
+        assert!(!glob_match("GetAuthors", "GetAuthor"));
+        assert!(!glob_match("Get*Row", "GetAuthor"));
+        assert!(!glob_match("*Row", "RowCount"));
+        assert!(!glob_match("getauthor", "GetAuthor"));
+        assert!(!glob_match("", "GetAuthor"));
+    }
+
+    #[test]
+    fn attrs_match_db_and_rust_names() {
+        let config = [(
+            "SetBook*".to_string(),
+            ItemAttributes {
+                row: vec!["#[derive(Hash)]".to_string()],
+                query: vec!["#[derive(Default)]".to_string()],
+                ..Default::default()
+            },
+        )]
+        .into_iter()
+        .collect();
+        let attributes = AttributeMap::new(&config).unwrap();
+
+        let row = attributes.row_attrs("UpdateBookISBN", "SetBookIsbn");
+        assert_eq!(row.to_string(), "# [derive (Hash)]");
+        let query = attributes.query_attrs("UpdateBookISBN", "SetBookIsbn");
+        assert_eq!(query.to_string(), "# [derive (Default)]");
+        assert!(attributes.row_attrs("GetBook", "GetBook").is_empty());
+    }
+}
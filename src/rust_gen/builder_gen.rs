@@ -1,3 +1,4 @@
+use crate::directive::Visibility;
 use crate::rust_gen::param_gen::PgParams;
 use crate::user_type::TypeMap;
 use proc_macro2::TokenStream;
@@ -9,11 +10,18 @@ use syn::Ident;
 #[derive(Debug, Clone)]
 pub(crate) struct PostgresBuilderGen {
     query_name: String,
+    visibility: Visibility,
+    /// Emit `build_owned` producing a `'static` query
+    emit_owned: bool,
 }
 
 impl PostgresBuilderGen {
-    pub(crate) fn new(query_name: String) -> Self {
-        Self { query_name }
+    pub(crate) fn new(query_name: String, visibility: Visibility, emit_owned: bool) -> Self {
+        Self {
+            query_name,
+            visibility,
+            emit_owned,
+        }
     }
 
     /// Generate the complete builder pattern (type-state or Option-based)
@@ -80,9 +88,10 @@ impl PostgresBuilderGen {
         };
 
         // Generate builder struct
+        let visibility = self.visibility;
         let builder_struct = quote! {
             #[derive(Debug)]
-            pub struct #builder_ident #lifetime_param {
+            #visibility struct #builder_ident #lifetime_param {
                 fields: Fields,
                 phantom: #phantom_type,
             }
@@ -120,7 +129,7 @@ impl PostgresBuilderGen {
             .params
             .iter()
             .map(|param| {
-                let field_name = Ident::new(&param.inner.name, proc_macro2::Span::call_site());
+                let field_name = crate::utils::rust_ident(&param.inner.name);
                 if param.is_copy_cheap_type(type_map) {
                     let rs_type = &param.inner.rs_type;
                     if param.inner.is_nullable {
@@ -156,8 +165,8 @@ impl PostgresBuilderGen {
             .params
             .iter()
             .map(|param| {
-                let method_name = Ident::new(&param.inner.name, proc_macro2::Span::call_site());
-                let field_name = Ident::new(&param.inner.name, proc_macro2::Span::call_site());
+                let method_name = crate::utils::rust_ident(&param.inner.name);
+                let field_name = crate::utils::rust_ident(&param.inner.name);
 
                 if param.is_copy_cheap_type(type_map) {
                     let param_type = if param.inner.is_nullable {
@@ -208,7 +217,7 @@ impl PostgresBuilderGen {
             .params
             .iter()
             .map(|param| {
-                let field_name = Ident::new(&param.inner.name, proc_macro2::Span::call_site());
+                let field_name = crate::utils::rust_ident(&param.inner.name);
                 quote! {
                     #field_name: self.#field_name.expect("Missing required field")
                 }
@@ -221,9 +230,18 @@ impl PostgresBuilderGen {
             quote! { #struct_ident }
         };
 
+        let build_owned = (self.emit_owned && has_lifetime).then(|| {
+            quote! {
+                pub fn build_owned(self) -> #struct_ident<'static> {
+                    self.build().into_owned()
+                }
+            }
+        });
+
+        let visibility = self.visibility;
         quote! {
             #[derive(Debug, Default)]
-            pub struct #builder_ident #lifetime_param {
+            #visibility struct #builder_ident #lifetime_param {
                 #(#builder_fields,)*
             }
 
@@ -241,17 +259,19 @@ impl PostgresBuilderGen {
                         #(#build_fields,)*
                     }
                 }
+
+                #build_owned
             }
         }
     }
 
     fn query_struct_ident(&self) -> Ident {
-        Ident::new(&self.query_name, proc_macro2::Span::call_site())
+        crate::utils::rust_ident(&self.query_name)
     }
 
     fn builder_struct_ident(&self) -> Ident {
         let builder_name = format!("{}Builder", self.query_name);
-        Ident::new(&builder_name, proc_macro2::Span::call_site())
+        crate::utils::rust_ident(&builder_name)
     }
 
     fn needs_lifetime(&self, query_params: &PgParams, type_map: &impl TypeMap) -> bool {
@@ -325,7 +345,7 @@ impl PostgresBuilderGen {
         let mut methods = quote! {};
 
         for (param_index, param) in query_params.params.iter().enumerate() {
-            let method_ident = Ident::new(&param.inner.name, proc_macro2::Span::call_site());
+            let method_ident = crate::utils::rust_ident(&param.inner.name);
 
             // Generate parameter type
             let param_type = if param.is_copy_cheap_type(type_map) {
@@ -450,7 +470,7 @@ impl PostgresBuilderGen {
         let field_names: Vec<Ident> = query_params
             .params
             .iter()
-            .map(|param| Ident::new(&param.inner.name, proc_macro2::Span::call_site()))
+            .map(|param| crate::utils::rust_ident(&param.inner.name))
             .collect();
 
         let field_tokens: Vec<TokenStream> =
@@ -549,7 +569,7 @@ impl PostgresBuilderGen {
             .map(|i| {
                 if i == setting_index {
                     // Use the actual method parameter name
-                    let method_ident = Ident::new(param_name, proc_macro2::Span::call_site());
+                    let method_ident = crate::utils::rust_ident(param_name);
                     quote! { #method_ident }
                 } else {
                     let var_name = format!("v{}", i);
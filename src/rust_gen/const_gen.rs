@@ -1,4 +1,6 @@
+use crate::directive::Visibility;
 use crate::sqlc::QueryAnnotation;
+use crate::utils::Renames;
 use crate::{plugin, utils};
 use quote::quote;
 
@@ -14,13 +16,17 @@ pub(crate) trait GenericConstQuery {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) struct PostgresConstQuery {
     name: String,
+    /// Name of the SQL constant built from the renamed query name
+    const_name: String,
     comment: String,
     query: String,
+    /// `None` keeps the SQL constants private to the module
+    visibility: Option<Visibility>,
 }
 
 impl RustSelfIdent for PostgresConstQuery {
     fn ident_str(&self) -> String {
-        utils::rust_const_ident(&self.name)
+        self.const_name.clone()
     }
 }
 
@@ -31,25 +37,39 @@ impl GenericConstQuery for PostgresConstQuery {
 }
 
 impl PostgresConstQuery {
-    pub(crate) fn new(query: &plugin::Query, query_type: &QueryAnnotation) -> Self {
+    pub(crate) fn new(
+        query: &plugin::Query,
+        query_type: &QueryAnnotation,
+        visibility: Option<Visibility>,
+        renames: &Renames,
+    ) -> Self {
         let name = query.name.clone();
+        let const_name = utils::rust_const_ident(&name, renames);
         let comment = format!("-- name: {} {}\n", name, query_type);
 
         Self {
             name,
+            const_name,
             comment,
             query: query.text.clone(),
+            visibility,
         }
     }
 
+    /// Query name in the SQL file
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
     pub(crate) fn to_tokens(&self) -> crate::Result<proc_macro2::TokenStream> {
         let ident = self.ident();
         let raw_str = format!("r#\"{}\"#", self.sql_str());
         let raw_literal = raw_str.parse::<proc_macro2::TokenStream>().map_err(|_| {
             crate::Error::any_error(format!("Failed to parse raw literal({})", raw_str))
         })?;
+        let visibility = self.visibility;
         Ok(quote! {
-            pub const #ident: &str = #raw_literal;
+            #visibility const #ident: &str = #raw_literal;
         })
     }
 
@@ -58,8 +78,9 @@ impl PostgresConstQuery {
         let raw_literal = raw_str.parse::<proc_macro2::TokenStream>().map_err(|_| {
             crate::Error::any_error(format!("Failed to parse raw literal({})", raw_str))
         })?;
+        let visibility = self.visibility;
         Ok(quote! {
-            pub const QUERY: &'static str = #raw_literal;
+            #visibility const QUERY: &'static str = #raw_literal;
         })
     }
 }
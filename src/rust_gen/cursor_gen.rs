@@ -0,0 +1,96 @@
+use crate::db_support::DbCrate;
+use crate::directive::Visibility;
+use crate::query::QueryGenOptions;
+use crate::rust_gen::param_gen::PgParams;
+use crate::rust_gen::struct_gen::PgStruct;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+use syn::ext::IdentExt;
+
+/// Generates the `RowCursor` type shared by every `:many` cursor function
+///
+/// The cursor wraps a portal bound inside a transaction and fetches at most
+/// `batch_size` rows per round trip.
+pub(crate) fn generate_row_cursor(db_crate: DbCrate, visibility: Visibility) -> TokenStream {
+    let transaction_ident = db_crate.transaction_ident();
+    let portal_ident = db_crate.portal_ident();
+    let row_ident = db_crate.row_ident();
+    let error_ident = db_crate.error_ident();
+    let async_ident = db_crate.async_ident();
+    let await_def = db_crate.await_ident();
+    let transaction_ref = match db_crate {
+        DbCrate::TokioPostgres | DbCrate::DeadPoolPostgres => quote! { &'t #transaction_ident<'c> },
+        DbCrate::Postgres => quote! { &'t mut #transaction_ident<'c> },
+    };
+
+    quote! {
+        /// Server-side cursor yielding the rows of a `:many` query in batches
+        #visibility struct RowCursor<'t, 'c, T> {
+            transaction: #transaction_ref,
+            portal: #portal_ident,
+            batch_size: i32,
+            done: bool,
+            from_row: fn(&#row_ident) -> Result<T, #error_ident>,
+        }
+
+        impl<'t, 'c, T> RowCursor<'t, 'c, T> {
+            /// Fetches the next batch of rows; an empty batch means the cursor is exhausted
+            pub #async_ident fn next_batch(&mut self) -> Result<Vec<T>, #error_ident> {
+                if self.done {
+                    return Ok(Vec::new());
+                }
+                let rows = self
+                    .transaction
+                    .query_portal(&self.portal, self.batch_size)#await_def?;
+                self.done = rows.len() < self.batch_size as usize;
+                rows.iter().map(self.from_row).collect()
+            }
+        }
+    }
+}
+
+/// Generates `xxx_cursor` binding a `:many` query to a portal of the given transaction
+pub(crate) fn generate_cursor_fn(
+    db_crate: DbCrate,
+    options: &QueryGenOptions,
+    func_ident: &Ident,
+    query: TokenStream,
+    returning_row: &PgStruct,
+    query_params: &PgParams,
+    args: TokenStream,
+) -> TokenStream {
+    let visibility = options.visibility;
+    let cursor_ident = format_ident!("{}_cursor", func_ident.unraw());
+    let transaction_ident = db_crate.transaction_ident();
+    let error_ident = db_crate.error_ident();
+    let async_ident = db_crate.async_ident();
+    let await_def = db_crate.await_ident();
+    let returning_ident = returning_row.return_type();
+    let from_row = returning_row.decode_row_fn();
+    let params = query_params.to_stmt_params();
+    let (prepare, stmt) = query_params.to_statement(query, db_crate, options.prepare_typed);
+    let transaction_ref = match db_crate {
+        DbCrate::TokioPostgres | DbCrate::DeadPoolPostgres => quote! { &'t #transaction_ident<'c> },
+        DbCrate::Postgres => quote! { &'t mut #transaction_ident<'c> },
+    };
+
+    quote! {
+        #visibility #async_ident fn #cursor_ident<'t, 'c>(
+            client: #transaction_ref,
+            batch_size: std::num::NonZeroU32,
+            #args
+        ) -> Result<RowCursor<'t, 'c, #returning_ident>, #error_ident> {
+            #prepare
+            let portal = client.bind(#stmt, #params)#await_def?;
+            Ok(RowCursor {
+                transaction: client,
+                portal,
+                // the protocol caps a fetch at `i32::MAX` rows
+                batch_size: i32::try_from(batch_size.get()).unwrap_or(i32::MAX),
+                done: false,
+                from_row: #from_row,
+            })
+        }
+    }
+}
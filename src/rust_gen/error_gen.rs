@@ -0,0 +1,227 @@
+use crate::db_support::DbCrate;
+use crate::directive::Visibility;
+use crate::error::Error;
+use crate::utils;
+use crate::utils::Renames;
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+/// `QueryError` generator classifying database errors by SQLSTATE
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresQueryError {
+    db_crate: DbCrate,
+    visibility: Visibility,
+    /// Constraint names known to the caller, e.g. `users_email_key`
+    constraints: Vec<String>,
+    /// `Constraint` variant of each name in `constraints`
+    variants: Vec<Ident>,
+}
+
+impl PostgresQueryError {
+    pub(crate) fn new(
+        db_crate: DbCrate,
+        visibility: Visibility,
+        constraints: Vec<String>,
+        renames: &Renames,
+    ) -> Result<Self, Error> {
+        let mut variants: Vec<Ident> = Vec::with_capacity(constraints.len());
+        for name in &constraints {
+            let variant = utils::rust_ident(&utils::rust_value_ident(name, renames));
+            // `Other` holds the constraints that were not listed
+            if variant == "Other" || variants.contains(&variant) {
+                return Err(Error::invalid_option(
+                    "constraints",
+                    format!("{} (duplicate variant `{}`)", name, variant),
+                ));
+            }
+            variants.push(variant);
+        }
+
+        Ok(Self {
+            db_crate,
+            visibility,
+            constraints,
+            variants,
+        })
+    }
+
+    pub(crate) fn generate(&self) -> TokenStream {
+        let constraint = self.generate_constraint();
+        let query_error = self.generate_query_error();
+
+        quote! {
+            #constraint
+            #query_error
+        }
+    }
+
+    fn generate_constraint(&self) -> TokenStream {
+        let visibility = self.visibility;
+        let variants = &self.variants;
+        let names = &self.constraints;
+
+        quote! {
+            /// Database constraint named in a constraint violation
+            #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+            #visibility enum Constraint {
+                #(#variants,)*
+                Other(String),
+            }
+
+            impl Constraint {
+                pub fn from_name(name: &str) -> Self {
+                    match name {
+                        #(#names => Self::#variants,)*
+                        _ => Self::Other(name.to_owned()),
+                    }
+                }
+
+                pub fn name(&self) -> &str {
+                    match self {
+                        #(Self::#variants => #names,)*
+                        Self::Other(name) => name,
+                    }
+                }
+            }
+        }
+    }
+
+    fn generate_query_error(&self) -> TokenStream {
+        let visibility = self.visibility;
+        let error_ident = self.db_crate.error_ident();
+        let sqlstate_ident = self.db_crate.sqlstate_ident();
+
+        quote! {
+            /// Database error classified by its SQLSTATE code
+            #[derive(Debug)]
+            #visibility enum QueryError {
+                UniqueViolation {
+                    constraint: Option<Constraint>,
+                    source: #error_ident,
+                },
+                ForeignKeyViolation {
+                    constraint: Option<Constraint>,
+                    source: #error_ident,
+                },
+                NotNullViolation {
+                    column: Option<String>,
+                    source: #error_ident,
+                },
+                CheckViolation {
+                    constraint: Option<Constraint>,
+                    source: #error_ident,
+                },
+                ExclusionViolation {
+                    constraint: Option<Constraint>,
+                    source: #error_ident,
+                },
+                SerializationFailure {
+                    source: #error_ident,
+                },
+                Deadlock {
+                    source: #error_ident,
+                },
+                QueryCanceled {
+                    source: #error_ident,
+                },
+                Other(#error_ident),
+            }
+
+            impl QueryError {
+                /// Returns the underlying driver error
+                pub fn inner(&self) -> &#error_ident {
+                    match self {
+                        Self::UniqueViolation { source, .. }
+                        | Self::ForeignKeyViolation { source, .. }
+                        | Self::NotNullViolation { source, .. }
+                        | Self::CheckViolation { source, .. }
+                        | Self::ExclusionViolation { source, .. }
+                        | Self::SerializationFailure { source }
+                        | Self::Deadlock { source }
+                        | Self::QueryCanceled { source }
+                        | Self::Other(source) => source,
+                    }
+                }
+
+                /// Whether the transaction may succeed when retried
+                pub fn is_retryable(&self) -> bool {
+                    matches!(self, Self::SerializationFailure { .. } | Self::Deadlock { .. })
+                }
+            }
+
+            impl From<#error_ident> for QueryError {
+                fn from(source: #error_ident) -> Self {
+                    let Some(db_error) = source.as_db_error() else {
+                        return Self::Other(source);
+                    };
+                    let constraint = db_error.constraint().map(Constraint::from_name);
+                    let code = db_error.code();
+                    if *code == #sqlstate_ident::UNIQUE_VIOLATION {
+                        Self::UniqueViolation { constraint, source }
+                    } else if *code == #sqlstate_ident::FOREIGN_KEY_VIOLATION {
+                        Self::ForeignKeyViolation { constraint, source }
+                    } else if *code == #sqlstate_ident::NOT_NULL_VIOLATION {
+                        let column = db_error.column().map(str::to_owned);
+                        Self::NotNullViolation { column, source }
+                    } else if *code == #sqlstate_ident::CHECK_VIOLATION {
+                        Self::CheckViolation { constraint, source }
+                    } else if *code == #sqlstate_ident::EXCLUSION_VIOLATION {
+                        Self::ExclusionViolation { constraint, source }
+                    } else if *code == #sqlstate_ident::T_R_SERIALIZATION_FAILURE {
+                        Self::SerializationFailure { source }
+                    } else if *code == #sqlstate_ident::T_R_DEADLOCK_DETECTED {
+                        Self::Deadlock { source }
+                    } else if *code == #sqlstate_ident::QUERY_CANCELED {
+                        Self::QueryCanceled { source }
+                    } else {
+                        Self::Other(source)
+                    }
+                }
+            }
+
+            impl std::fmt::Display for QueryError {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    std::fmt::Display::fmt(self.inner(), f)
+                }
+            }
+
+            impl std::error::Error for QueryError {
+                fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                    Some(self.inner())
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query_error(constraints: &[&str]) -> Result<PostgresQueryError, Error> {
+        PostgresQueryError::new(
+            DbCrate::TokioPostgres,
+            Visibility::default(),
+            constraints.iter().map(|name| name.to_string()).collect(),
+            &Renames::default(),
+        )
+    }
+
+    #[test]
+    fn constraint_variants_are_escaped() {
+        let query_error = query_error(&["users_email_key", "self"]).unwrap();
+        let variants = query_error
+            .variants
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(variants, ["UsersEmailKey", "Self_"]);
+    }
+
+    #[test]
+    fn colliding_constraints_are_rejected() {
+        assert!(query_error(&["users_email_key", "UsersEmailKey"]).is_err());
+        assert!(query_error(&["other"]).is_err());
+    }
+}
@@ -1,11 +1,18 @@
 use crate::db_support::DbCrate;
+use crate::directive::{Visibility, is_directive};
+use crate::query::QueryGenOptions;
 use crate::rust_gen::const_gen::PostgresConstQuery;
+use crate::rust_gen::cursor_gen::generate_cursor_fn;
+use crate::rust_gen::instrument_gen::{QuerySpan, RowCount};
 use crate::rust_gen::naming::RustSelfIdent;
+use crate::rust_gen::timeout_gen::wrap_timeout;
 use crate::sqlc::QueryAnnotation;
 use crate::utils;
+use crate::utils::Renames;
 use proc_macro2::Span;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::Ident;
+use syn::ext::IdentExt;
 
 use super::param_gen::PgParams;
 use super::struct_gen::PgStruct;
@@ -14,8 +21,14 @@ use super::struct_gen::PgStruct;
 #[derive(Debug, Clone)]
 pub(crate) struct PostgresFunc {
     query_name: String,
+    /// Name of the query struct the functions with parameters delegate to
+    struct_name: String,
     annotation: QueryAnnotation,
     db_crate: DbCrate,
+    options: QueryGenOptions,
+    span: QuerySpan,
+    /// Attributes such as `#[deprecated]` of the query functions
+    attrs: proc_macro2::TokenStream,
 }
 
 impl PostgresFunc {
@@ -23,12 +36,21 @@ impl PostgresFunc {
         query: &crate::plugin::Query,
         annotation: QueryAnnotation,
         db_crate: DbCrate,
+        options: QueryGenOptions,
+        attrs: proc_macro2::TokenStream,
+        renames: &Renames,
     ) -> Self {
-        let query_name = utils::rust_fn_ident(&query.name);
+        let query_name = utils::rust_fn_ident(&query.name, renames);
+        let struct_name = utils::rust_value_ident(&query.name, renames);
+        let docs = query_docs(query);
         Self {
             query_name,
+            struct_name,
             annotation,
             db_crate,
+            options,
+            span: QuerySpan::new(query),
+            attrs: quote! { #docs #attrs },
         }
     }
 
@@ -42,12 +64,32 @@ impl PostgresFunc {
         let args = query_params.to_func_args(type_map);
 
         let async_ident = self.db_crate.async_ident();
+        let attrs = &self.attrs;
+        let visibility = self.options.visibility;
 
         quote! {
-            pub #async_ident fn #func_ident(client:#client_ident,#args)
+            #attrs
+            #visibility #async_ident fn #func_ident(client:#client_ident,#args)
         }
     }
 
+    /// Wraps a function body with the configured timeout and instrumentation
+    fn instrument(
+        &self,
+        query_ident: &Ident,
+        row_count: RowCount,
+        body: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let body = wrap_timeout(self.db_crate, self.options.timeout, body);
+        self.span.wrap(
+            &self.options,
+            self.db_crate,
+            quote! { #query_ident },
+            row_count,
+            body,
+        )
+    }
+
     fn generate_exec(
         &self,
         query_const: &PostgresConstQuery,
@@ -60,9 +102,22 @@ impl PostgresFunc {
 
         let query_ident = query_const.ident();
         let params = query_params.to_stmt_params();
+        let (prepare, stmt) = query_params.to_statement(
+            quote! {#query_ident},
+            self.db_crate,
+            self.options.prepare_typed,
+        );
+        let body = self.instrument(
+            &query_ident,
+            RowCount::Affected,
+            quote! {
+                #prepare
+                client.execute(#stmt,#params)#await_def
+            },
+        );
         quote! {
             #func_def -> Result<u64,#error_ident> {
-                client.execute(#query_ident,#params)#await_def
+                #body
             }
         }
     }
@@ -76,22 +131,20 @@ impl PostgresFunc {
     ) -> proc_macro2::TokenStream {
         let func_def = self.func_def(query_params, type_map);
         let error_ident = self.db_crate.error_ident();
-        let returning_ident = returning_row.ident();
+        let returning_ident = returning_row.return_type();
 
         // If there are parameters, use the struct API internally
         if !query_params.params.is_empty() {
-            let struct_ident = Ident::new(
-                &crate::utils::rust_value_ident(&self.query_name),
-                Span::call_site(),
-            );
+            let struct_ident = utils::rust_ident(&self.struct_name);
             let field_assignments = self.generate_struct_field_assignments(query_params, type_map);
+            let await_def = self.db_crate.await_ident();
 
             quote! {
                 #func_def -> Result<Option<#returning_ident>,#error_ident> {
                     let query_struct = #struct_ident {
                         #field_assignments
                     };
-                    query_struct.query_opt(client).await
+                    query_struct.query_opt(client)#await_def
                 }
             }
         } else {
@@ -100,14 +153,23 @@ impl PostgresFunc {
             let query_ident = query_const.ident();
             let params = query_params.to_stmt_params();
             let row_ident = Ident::new("row", Span::call_site());
+            let from_row = returning_row.decode_row(&row_ident);
 
-            quote! {
-                #func_def -> Result<Option<#returning_ident>,#error_ident> {
+            let body = self.instrument(
+                &query_ident,
+                RowCount::Optional,
+                quote! {
                     let #row_ident = client.query_opt(#query_ident,#params)#await_def?;
                     match #row_ident {
-                        Some(ref #row_ident) => Ok(Some(#returning_ident::from_row(#row_ident)?)),
+                        Some(#row_ident) => Ok(Some(#from_row?)),
                         None => Ok(None),
                     }
+                },
+            );
+
+            quote! {
+                #func_def -> Result<Option<#returning_ident>,#error_ident> {
+                    #body
                 }
             }
         }
@@ -126,16 +188,97 @@ impl PostgresFunc {
         let error_ident = self.db_crate.error_ident();
 
         let query_ident = query_const.ident();
-        let returning_ident = returning_row.ident();
+        let returning_ident = returning_row.return_type();
         let params = query_params.to_stmt_params();
 
         let rows_ident = Ident::new("rows", Span::call_site());
         let row_ident = Ident::new("r", Span::call_site());
+        let from_row = returning_row.decode_row(&row_ident);
+        let (prepare, stmt) = query_params.to_statement(
+            quote! {#query_ident},
+            self.db_crate,
+            self.options.prepare_typed,
+        );
+
+        let body = self.instrument(
+            &query_ident,
+            RowCount::Len,
+            quote! {
+                #prepare
+                let #rows_ident = client.query(#stmt,#params)#await_def?;
+                Ok(#rows_ident.into_iter().map(|#row_ident| #from_row))
+            },
+        );
+
+        let stream_func = if self.options.emit_streaming {
+            self.generate_stream(query_const, returning_row, query_params, type_map)
+        } else {
+            quote! {}
+        };
+
+        let cursor_func = if self.options.emit_cursors {
+            let cursor_fn = generate_cursor_fn(
+                self.db_crate,
+                &self.options,
+                &self.ident(),
+                quote! {#query_ident},
+                returning_row,
+                query_params,
+                query_params.to_func_args(type_map),
+            );
+            let attrs = &self.attrs;
+            quote! {
+                #attrs
+                #cursor_fn
+            }
+        } else {
+            quote! {}
+        };
 
         quote! {
             #func_def -> Result<impl Iterator<Item = Result<#returning_ident,#error_ident>>,#error_ident> {
-                let #rows_ident = client.query(#query_ident,#params)#await_def?;
-                Ok(#rows_ident.into_iter().map(|#row_ident| #returning_ident::from_row(&#row_ident)))
+                #body
+            }
+            #stream_func
+            #cursor_func
+        }
+    }
+
+    /// Streaming variant of a `:many` query that does not buffer the rows
+    ///
+    /// Async crates return a `Stream` named `xxx_stream` and the sync crate returns
+    /// a lazy iterator named `xxx_iter` borrowing the client.
+    fn generate_stream(
+        &self,
+        query_const: &PostgresConstQuery,
+        returning_row: &PgStruct,
+        query_params: &PgParams,
+        type_map: &impl crate::user_type::TypeMap,
+    ) -> proc_macro2::TokenStream {
+        let args = query_params.to_func_args(type_map);
+        let query_ident = query_const.ident();
+        let params = query_params.to_stmt_params();
+        let (prepare, stmt) = query_params.to_statement(
+            quote! {#query_ident},
+            self.db_crate,
+            self.options.prepare_typed,
+        );
+        let body = generate_row_stream(self.db_crate, &stmt, &params, returning_row);
+        let sig = stream_sig(
+            self.db_crate,
+            self.options.visibility,
+            &self.ident(),
+            quote! {},
+            args,
+            returning_row,
+        );
+        let attrs = &self.attrs;
+
+        quote! {
+            #attrs
+            #sig {
+                #prepare
+                #body
             }
         }
     }
@@ -149,8 +292,8 @@ impl PostgresFunc {
 
         for param in query_params.params.iter() {
             let field_name = &param.inner.name;
-            let field_ident = Ident::new(field_name, Span::call_site());
-            let param_ident = Ident::new(field_name, Span::call_site());
+            let field_ident = utils::rust_ident(field_name);
+            let param_ident = utils::rust_ident(field_name);
 
             if param.is_copy_cheap_type(type_map) {
                 // Copy-cheap types: direct assignment
@@ -203,3 +346,81 @@ impl RustSelfIdent for PostgresFunc {
         self.query_name.clone()
     }
 }
+
+/// Documentation of the query functions: the query comments and the SQL text
+fn query_docs(query: &crate::plugin::Query) -> proc_macro2::TokenStream {
+    let comments = query
+        .comments
+        .iter()
+        .map(String::as_str)
+        .filter(|comment| !is_directive(comment))
+        .collect::<Vec<_>>();
+    let separator = if comments.is_empty() { None } else { Some("") };
+    let sql = ["<details><summary>SQL</summary>", "", "```sql"]
+        .into_iter()
+        .chain(query.text.lines())
+        .chain(["```", "", "</details>"]);
+
+    utils::doc_attrs(comments.into_iter().chain(separator).chain(sql))
+}
+
+/// Signature of a streaming `:many` function taking `client` after `receiver`
+pub(crate) fn stream_sig(
+    db_crate: DbCrate,
+    visibility: Visibility,
+    base_ident: &Ident,
+    receiver: proc_macro2::TokenStream,
+    args: proc_macro2::TokenStream,
+    returning_row: &PgStruct,
+) -> proc_macro2::TokenStream {
+    let generic_client = db_crate.generic_client_ident();
+    let error_ident = db_crate.error_ident();
+    let returning_ident = returning_row.return_type();
+
+    match db_crate {
+        DbCrate::TokioPostgres | DbCrate::DeadPoolPostgres => {
+            let func_ident = format_ident!("{}_stream", base_ident.unraw());
+            quote! {
+                #visibility async fn #func_ident(#receiver client: &impl #generic_client, #args)
+                    -> Result<impl futures_util::Stream<Item = Result<#returning_ident, #error_ident>>, #error_ident>
+            }
+        }
+        DbCrate::Postgres => {
+            let func_ident = format_ident!("{}_iter", base_ident.unraw());
+            quote! {
+                #visibility fn #func_ident<'c>(#receiver client: &'c mut impl #generic_client, #args)
+                    -> Result<impl Iterator<Item = Result<#returning_ident, #error_ident>> + 'c, #error_ident>
+            }
+        }
+    }
+}
+
+/// Body of a streaming `:many` function built on `query_raw`
+pub(crate) fn generate_row_stream(
+    db_crate: DbCrate,
+    stmt: &proc_macro2::TokenStream,
+    params: &proc_macro2::TokenStream,
+    returning_row: &PgStruct,
+) -> proc_macro2::TokenStream {
+    let to_sql = db_crate.tosql_ident();
+    let from_row = returning_row.decode_row(&Ident::new("row", Span::call_site()));
+    let await_def = db_crate.await_ident();
+
+    let rows = quote! {
+        let params: &[&(dyn #to_sql + Sync)] = #params;
+        let rows = client.query_raw(#stmt, params.iter().copied())#await_def?;
+    };
+    match db_crate {
+        DbCrate::TokioPostgres | DbCrate::DeadPoolPostgres => quote! {
+            #rows
+            Ok(futures_util::StreamExt::map(rows, |row| {
+                row.and_then(|row| #from_row)
+            }))
+        },
+        DbCrate::Postgres => quote! {
+            #rows
+            Ok(postgres::fallible_iterator::FallibleIterator::iterator(rows)
+                .map(|row| row.and_then(|row| #from_row)))
+        },
+    }
+}
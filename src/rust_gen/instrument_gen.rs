@@ -0,0 +1,263 @@
+use crate::db_support::DbCrate;
+use crate::directive::Visibility;
+use crate::plugin;
+use crate::query::QueryGenOptions;
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::{Deserialize, Deserializer};
+
+/// Instrumentation wrapped around generated query functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Instrument {
+    #[default]
+    None,
+    Tracing,
+}
+
+impl<'de> Deserialize<'de> for Instrument {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "none" => Ok(Instrument::None),
+            "tracing" => Ok(Instrument::Tracing),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown instrument: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// How the number of rows is read from a successful result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RowCount {
+    /// `u64` returned by `execute`
+    Affected,
+    /// Single row
+    One,
+    /// `Option` of a row
+    Optional,
+    /// `Vec` or exact size iterator of rows
+    Len,
+}
+
+impl RowCount {
+    /// Expression computing the `u64` row count from `value`, a reference to the `Ok` value
+    fn to_expr(self) -> TokenStream {
+        match self {
+            RowCount::Affected => quote! { *value },
+            RowCount::One => quote! { 1u64 },
+            RowCount::Optional => quote! { u64::from(value.is_some()) },
+            RowCount::Len => quote! { value.len() as u64 },
+        }
+    }
+
+    fn value_binding(self) -> TokenStream {
+        match self {
+            RowCount::One => quote! { _value },
+            _ => quote! { value },
+        }
+    }
+}
+
+/// Instrumentation of one query shared by its functions and struct methods
+#[derive(Debug, Clone)]
+pub(crate) struct QuerySpan {
+    /// Span name, the query name as written in the SQL file
+    name: String,
+    /// Query annotation such as `:one`
+    annotation: String,
+    /// Leading SQL keyword such as `SELECT`
+    operation: String,
+}
+
+impl QuerySpan {
+    pub(crate) fn new(query: &plugin::Query) -> Self {
+        let operation = query
+            .text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with("--"))
+            .flat_map(str::split_whitespace)
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches('(')
+            .to_uppercase();
+        Self {
+            name: query.name.clone(),
+            annotation: query.cmd.clone(),
+            operation,
+        }
+    }
+
+    /// Wraps `body` with the instrumentation enabled in `options`
+    ///
+    /// `body` must evaluate to the function's `Result`. `statement` is the SQL text
+    /// recorded when `instrument_statement` is enabled.
+    pub(crate) fn wrap(
+        &self,
+        options: &QueryGenOptions,
+        db_crate: DbCrate,
+        statement: TokenStream,
+        row_count: RowCount,
+        body: TokenStream,
+    ) -> TokenStream {
+        let statement = options.instrument_statement.then_some(statement);
+        let body = match options.instrument {
+            Instrument::None => body,
+            Instrument::Tracing => self.wrap_tracing(db_crate, statement, row_count, body),
+        };
+        if options.emit_query_observer {
+            self.wrap_observer(db_crate, row_count, body)
+        } else {
+            body
+        }
+    }
+
+    /// Runs `body` in a `tracing` span recording the row count or the error
+    fn wrap_tracing(
+        &self,
+        db_crate: DbCrate,
+        statement: Option<TokenStream>,
+        row_count: RowCount,
+        body: TokenStream,
+    ) -> TokenStream {
+        let name = &self.name;
+        let operation = &self.operation;
+        let statement = statement
+            .map(|statement| quote! { db.statement = #statement, })
+            .unwrap_or_default();
+        let rows_field = match row_count {
+            RowCount::Affected => "db.rows_affected",
+            _ => "db.response.returned_rows",
+        };
+        let rows_field_ident = rows_field
+            .split('.')
+            .map(|part| syn::Ident::new(part, proc_macro2::Span::call_site()));
+        let value_binding = row_count.value_binding();
+        let rows = row_count.to_expr();
+
+        let run = match db_crate {
+            DbCrate::TokioPostgres | DbCrate::DeadPoolPostgres => quote! {
+                tracing::Instrument::instrument(async { #body }, span.clone()).await
+            },
+            DbCrate::Postgres => quote! {
+                span.in_scope(|| { #body })
+            },
+        };
+
+        quote! {
+            let span = tracing::info_span!(
+                #name,
+                db.system = "postgresql",
+                db.operation = #operation,
+                #statement
+                #(#rows_field_ident).* = tracing::field::Empty,
+                error = tracing::field::Empty,
+            );
+            let result = #run;
+            match &result {
+                Ok(#value_binding) => {
+                    span.record(#rows_field, #rows);
+                }
+                Err(err) => {
+                    span.record("error", tracing::field::display(err));
+                }
+            }
+            result
+        }
+    }
+
+    /// Reports the start and the outcome of `body` to the global `QueryObserver`
+    fn wrap_observer(
+        &self,
+        db_crate: DbCrate,
+        row_count: RowCount,
+        body: TokenStream,
+    ) -> TokenStream {
+        let name = &self.name;
+        let annotation = &self.annotation;
+        let value_binding = row_count.value_binding();
+        let rows = row_count.to_expr();
+
+        let run = match db_crate {
+            DbCrate::TokioPostgres | DbCrate::DeadPoolPostgres => quote! {
+                async { #body }.await
+            },
+            DbCrate::Postgres => quote! {
+                (|| { #body })()
+            },
+        };
+
+        quote! {
+            let observer = QUERY_OBSERVER.get();
+            if let Some(observer) = observer {
+                observer.on_start(#name, #annotation);
+            }
+            let started_at = std::time::Instant::now();
+            let result = #run;
+            if let Some(observer) = observer {
+                let (rows, error) = match &result {
+                    Ok(#value_binding) => (Some(#rows), None),
+                    Err(err) => (None, Some(err)),
+                };
+                observer.on_finish(&QueryOutcome {
+                    name: #name,
+                    annotation: #annotation,
+                    duration: started_at.elapsed(),
+                    rows,
+                    error,
+                });
+            }
+            result
+        }
+    }
+}
+
+/// Generates the `QueryObserver` trait and the global observer slot
+pub(crate) fn generate_query_observer(db_crate: DbCrate, visibility: Visibility) -> TokenStream {
+    let error_ident = db_crate.error_ident();
+
+    quote! {
+        /// Outcome of one query execution
+        #[derive(Debug)]
+        #visibility struct QueryOutcome<'a> {
+            /// Query name such as `GetAuthor`
+            pub name: &'static str,
+            /// Query annotation such as `:one`
+            pub annotation: &'static str,
+            pub duration: std::time::Duration,
+            /// Rows returned, or affected by `:exec` queries, when the query succeeded
+            pub rows: Option<u64>,
+            pub error: Option<&'a #error_ident>,
+        }
+
+        /// Hooks called around every generated query, e.g. to export metrics
+        #visibility trait QueryObserver: Send + Sync {
+            fn on_start(&self, name: &'static str, annotation: &'static str) {
+                let _ = (name, annotation);
+            }
+
+            fn on_finish(&self, outcome: &QueryOutcome<'_>);
+        }
+
+        static QUERY_OBSERVER: std::sync::OnceLock<Box<dyn QueryObserver>> = std::sync::OnceLock::new();
+
+        /// Installs the global `QueryObserver`
+        ///
+        /// Returns the observer back if one was already installed. There is no
+        /// per-client observer: the driver's `GenericClient` trait is sealed, so a
+        /// client wrapper carrying one could not be passed to the query functions.
+        #visibility fn set_query_observer<O: QueryObserver + 'static>(observer: O) -> Result<(), O> {
+            let mut observer = Some(observer);
+            QUERY_OBSERVER.get_or_init(|| Box::new(observer.take().unwrap()));
+            match observer {
+                Some(observer) => Err(observer),
+                None => Ok(()),
+            }
+        }
+    }
+}
@@ -1,7 +1,17 @@
+pub mod attribute_gen;
 pub mod builder_gen;
 pub mod const_gen;
+pub mod cursor_gen;
+pub mod error_gen;
 pub mod func_gen;
+pub mod instrument_gen;
 pub mod naming;
 pub mod param_gen;
+pub mod prepared_gen;
+pub mod querier_gen;
+pub mod query_trait_gen;
+pub mod serde_gen;
 pub mod struct_api_gen;
 pub mod struct_gen;
+pub mod timeout_gen;
+pub mod transaction_gen;
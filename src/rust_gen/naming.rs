@@ -1,5 +1,5 @@
 use crate::plugin;
-use proc_macro2::Span;
+use crate::utils::{Renames, rust_struct_field};
 use std::collections::{HashMap, HashSet};
 use syn::Ident;
 
@@ -7,7 +7,7 @@ use syn::Ident;
 pub(crate) trait RustSelfIdent {
     fn ident_str(&self) -> String;
     fn ident(&self) -> Ident {
-        Ident::new(&self.ident_str(), Span::call_site())
+        crate::utils::rust_ident(&self.ident_str())
     }
 }
 
@@ -27,39 +27,51 @@ pub(crate) fn get_table_identifier(column: &plugin::Column) -> Option<String> {
 }
 
 /// Gets prefix for field names based on table information
-pub(crate) fn get_field_prefix(column: &plugin::Column) -> Option<String> {
+pub(crate) fn get_field_prefix(column: &plugin::Column, renames: &Renames) -> Option<String> {
     if let Some(table) = &column.table {
-        if !column.table_alias.is_empty() {
+        let prefix = if !column.table_alias.is_empty() {
             // Use table alias if available (e.g., "e", "m" for self-joins)
-            Some(column.table_alias.clone())
+            &column.table_alias
         } else {
             // Use table name if no alias (e.g., "authors", "books")
-            Some(table.name.clone())
-        }
+            &table.name
+        };
+        Some(rust_struct_field(prefix, renames))
     } else {
         None
     }
 }
 
+/// Field name of a column without table prefix
+///
+/// This is the only place column names are renamed, the names built from it are not
+/// converted again.
+fn column_field_name(idx: usize, column: &plugin::Column, renames: &Renames) -> String {
+    if !column.name.is_empty() {
+        rust_struct_field(&column.name, renames)
+    } else {
+        format!("column_{}", idx)
+    }
+}
+
 /// Simulates field name generation with option for simple vs prefixed names
-pub(crate) fn simulate_field_names(query: &plugin::Query, use_simple_names: bool) -> Vec<String> {
+pub(crate) fn simulate_field_names(
+    query: &plugin::Query,
+    use_simple_names: bool,
+    renames: &Renames,
+) -> Vec<String> {
     query
         .columns
         .iter()
         .enumerate()
         .map(|(idx, col)| {
-            let name = if let Some(prefix) = get_field_prefix(col) {
-                if use_simple_names {
-                    col.name.clone()
-                } else {
-                    format!("{}_{}", prefix, col.name)
+            let name = column_field_name(idx, col, renames);
+            match get_field_prefix(col, renames) {
+                Some(prefix) if !use_simple_names && !col.name.is_empty() => {
+                    format!("{}_{}", prefix, name)
                 }
-            } else if !col.name.is_empty() {
-                col.name.clone()
-            } else {
-                format!("column_{}", idx)
-            };
-            crate::utils::rust_struct_field(&name)
+                _ => name,
+            }
         })
         .collect()
 }
@@ -81,7 +93,7 @@ pub(crate) fn has_single_table_identifier_basic(query: &plugin::Query) -> bool {
 }
 
 /// Determines if simple field names can be used without conflicts
-pub(crate) fn should_use_simple_names(query: &plugin::Query) -> bool {
+pub(crate) fn should_use_simple_names(query: &plugin::Query, renames: &Renames) -> bool {
     // Phase 1: Check if we have a single table identifier
     let single_table_identifier = has_single_table_identifier_basic(query);
 
@@ -90,29 +102,23 @@ pub(crate) fn should_use_simple_names(query: &plugin::Query) -> bool {
     }
 
     // Phase 2: Check for field name conflicts
-    let simple_field_names = simulate_field_names(query, true);
+    let simple_field_names = simulate_field_names(query, true, renames);
     !has_field_name_conflicts(&simple_field_names)
 }
 
 /// Wrapper that uses should_use_simple_names for single table detection
-pub(crate) fn has_single_table_identifier(query: &plugin::Query) -> bool {
-    should_use_simple_names(query)
+pub(crate) fn has_single_table_identifier(query: &plugin::Query, renames: &Renames) -> bool {
+    should_use_simple_names(query, renames)
 }
 
 /// Main algorithm for generating unique field names with conflict resolution
-pub(crate) fn generate_unique_field_names(query: &plugin::Query) -> Vec<String> {
+pub(crate) fn generate_unique_field_names(query: &plugin::Query, renames: &Renames) -> Vec<String> {
     // Step 1: Check for column name conflicts (ignoring table prefixes)
     let column_names: Vec<String> = query
         .columns
         .iter()
         .enumerate()
-        .map(|(idx, col)| {
-            if !col.name.is_empty() {
-                col.name.clone()
-            } else {
-                format!("column_{}", idx)
-            }
-        })
+        .map(|(idx, col)| column_field_name(idx, col, renames))
         .collect();
 
     let mut column_name_counts: HashMap<String, usize> = HashMap::new();
@@ -124,14 +130,8 @@ pub(crate) fn generate_unique_field_names(query: &plugin::Query) -> Vec<String>
     let tentative_names: Vec<String> = query
         .columns
         .iter()
-        .enumerate()
-        .map(|(idx, col)| {
-            let col_name = if !col.name.is_empty() {
-                col.name.clone()
-            } else {
-                format!("column_{}", idx)
-            };
-
+        .zip(column_names)
+        .map(|(col, col_name)| {
             let col_count = column_name_counts.get(&col_name).unwrap_or(&1);
 
             if *col_count <= 1 {
@@ -139,7 +139,7 @@ pub(crate) fn generate_unique_field_names(query: &plugin::Query) -> Vec<String>
                 col_name
             } else {
                 // Rule 2: Column name conflicts - use table_column format
-                if let Some(prefix) = get_field_prefix(col) {
+                if let Some(prefix) = get_field_prefix(col, renames) {
                     format!("{}_{}", prefix, col_name)
                 } else {
                     col_name
@@ -170,20 +170,22 @@ pub(crate) fn generate_unique_field_names(query: &plugin::Query) -> Vec<String>
                 format!("{}_{}", name, counter)
             }
         })
-        .map(|name| crate::utils::rust_struct_field(&name))
         .collect();
 
     final_names
 }
 
 /// Generates unique parameter names for SQL query parameters
-pub(crate) fn generate_unique_param_names(params: &[(i32, &plugin::Column)]) -> Vec<String> {
+pub(crate) fn generate_unique_param_names(
+    params: &[(i32, &plugin::Column)],
+    renames: &Renames,
+) -> Vec<String> {
     // First pass: generate initial names and count conflicts
     let initial_names: Vec<String> = params
         .iter()
         .map(|(_, col)| {
             if !col.name.is_empty() {
-                col.name.clone()
+                rust_struct_field(&col.name, renames)
             } else {
                 "param".to_string()
             }
@@ -204,13 +206,12 @@ pub(crate) fn generate_unique_param_names(params: &[(i32, &plugin::Column)]) ->
             let count = name_counts.get(name).unwrap_or(&1);
             if *count <= 1 {
                 // No conflict, use original name
-                crate::utils::rust_struct_field(name)
+                name.clone()
             } else {
                 // Conflict detected, append numerical suffix
                 let counter = name_counters.entry(name.clone()).or_insert(0);
                 *counter += 1;
-                let unique_name = format!("{}_{}", name, counter);
-                crate::utils::rust_struct_field(&unique_name)
+                format!("{}_{}", name, counter)
             }
         })
         .collect();
@@ -218,6 +219,24 @@ pub(crate) fn generate_unique_param_names(params: &[(i32, &plugin::Column)]) ->
     final_names
 }
 
+/// Rejects field names that become the same ident once escaped, e.g. `self` and `self_`
+pub(crate) fn check_escaped_field_names(
+    query_name: &str,
+    field_names: &[String],
+) -> crate::Result<()> {
+    let mut idents = HashSet::new();
+    for name in field_names {
+        let ident = crate::utils::rust_ident(name).to_string();
+        if !idents.insert(ident.clone()) {
+            return Err(crate::Error::any_error(format!(
+                "field `{}` is generated twice for query {}; rename one of the columns",
+                ident, query_name
+            )));
+        }
+    }
+    Ok(())
+}
+
 /// Helper to get column name from generated list with fallback
 pub(crate) fn column_name_from_list(field_names: &[String], idx: usize) -> String {
     field_names
@@ -225,3 +244,59 @@ pub(crate) fn column_name_from_list(field_names: &[String], idx: usize) -> Strin
         .cloned()
         .unwrap_or_else(|| format!("unknown_field_{}", idx))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    fn column(table: &str, name: &str) -> plugin::Column {
+        plugin::Column {
+            name: name.to_string(),
+            table: Some(plugin::Identifier {
+                name: table.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn renames_are_applied_once() {
+        let renames = Renames::new(
+            [("title", "name"), ("name", "title")]
+                .into_iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
+        );
+        let query = plugin::Query {
+            columns: vec![
+                column("books", "title"),
+                column("authors", "name"),
+                column("books", "book_id"),
+                column("authors", "book_id"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            generate_unique_field_names(&query, &renames),
+            ["name", "title", "books_book_id", "authors_book_id"]
+        );
+
+        let params = [(1, &query.columns[0]), (2, &query.columns[1])];
+        assert_eq!(
+            generate_unique_param_names(&params, &renames),
+            ["name", "title"]
+        );
+    }
+
+    #[test]
+    fn escaped_field_names() {
+        assert!(check_escaped_field_names("Q", &names(&["type", "type_", "self"])).is_ok());
+        assert!(check_escaped_field_names("Q", &names(&["self", "self_"])).is_err());
+        assert!(check_escaped_field_names("Q", &names(&["crate_", "crate"])).is_err());
+    }
+}
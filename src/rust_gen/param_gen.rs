@@ -1,10 +1,11 @@
-use crate::db_support::{PgColumn, PgColumnRef};
-use crate::rust_gen::naming::{RustSelfIdent, column_name_from_list, generate_unique_param_names};
+use crate::db_support::{DbCrate, PgColumn, PgColumnRef};
+use crate::rust_gen::naming::{
+    RustSelfIdent, check_escaped_field_names, column_name_from_list, generate_unique_param_names,
+};
 use crate::user_type::TypeMap;
 use crate::{plugin, utils};
-use proc_macro2::Span;
+use proc_macro2::TokenStream;
 use quote::quote;
-use syn::Ident;
 
 /// PostgreSQL parameters generator
 #[derive(Debug, Clone)]
@@ -27,7 +28,8 @@ impl PgParams {
             .ok_or_else(|| crate::Error::missing_col_info(&query.name))?;
 
         // Generate unique parameter names using dedicated function
-        let param_field_names = generate_unique_param_names(&params);
+        let param_field_names = generate_unique_param_names(&params, pg_map.renames());
+        check_escaped_field_names(&query.name, &param_field_names)?;
 
         let params = params
             .iter()
@@ -42,7 +44,7 @@ impl PgParams {
             })
             .map(|v| v.map(PgColumnRef::new))
             .collect::<crate::Result<Vec<_>>>()?;
-        let name = utils::rust_value_ident(&query.name);
+        let name = utils::rust_value_ident(&query.name, pg_map.renames());
         let name = format!("{}Params", name);
         Ok(Self { name, params })
     }
@@ -69,12 +71,64 @@ impl PgParams {
         let mut tokens = quote! {};
 
         for p in self.params.iter() {
-            let ident = Ident::new(&p.inner.name, Span::call_site());
+            let ident = utils::rust_ident(&p.inner.name);
             tokens = quote! {#tokens &#ident,}
         }
 
         quote! {&[#tokens]}
     }
+
+    /// Whether any parameter type must be looked up on the server
+    pub(crate) fn needs_type_lookup(&self) -> bool {
+        self.params.iter().any(|p| p.inner.needs_type_lookup())
+    }
+
+    /// Expression preparing `query` with explicit parameter types
+    ///
+    /// Types without a built-in constant are resolved on the server first, which
+    /// costs a round trip each.
+    pub(crate) fn to_prepare_typed(&self, query: TokenStream, db_crate: DbCrate) -> TokenStream {
+        let await_def = db_crate.await_ident();
+        let prepare_ident = match db_crate {
+            DbCrate::DeadPoolPostgres => quote! { prepare_typed_cached },
+            DbCrate::TokioPostgres | DbCrate::Postgres => quote! { prepare_typed },
+        };
+        let param_types = self.params.iter().map(|p| p.inner.pg_type_expr(db_crate));
+
+        // Lookups borrow the client, so they are evaluated before preparing
+        if self.needs_type_lookup() {
+            quote! {
+                {
+                    let param_types = [#(#param_types),*];
+                    client.#prepare_ident(#query, &param_types)#await_def?
+                }
+            }
+        } else {
+            quote! {
+                client.#prepare_ident(#query, &[#(#param_types),*])#await_def?
+            }
+        }
+    }
+
+    /// Statement to execute `query` with
+    ///
+    /// Returns the statements binding the prepared statement and the expression to
+    /// pass to the client. Without `prepare_typed` or parameters the query is passed
+    /// as is and the server infers the parameter types. The typed statement takes
+    /// the place of the one the client prepares for a query string.
+    pub(crate) fn to_statement(
+        &self,
+        query: TokenStream,
+        db_crate: DbCrate,
+        prepare_typed: bool,
+    ) -> (TokenStream, TokenStream) {
+        if !prepare_typed || self.params.is_empty() {
+            return (quote! {}, query);
+        }
+
+        let prepare = self.to_prepare_typed(query, db_crate);
+        (quote! { let stmt = #prepare; }, quote! { &stmt })
+    }
 }
 
 impl RustSelfIdent for PgParams {
@@ -0,0 +1,147 @@
+use crate::db_support::DbCrate;
+use crate::directive::Visibility;
+use crate::query::PostgresQuery;
+use crate::rust_gen::naming::RustSelfIdent;
+use crate::sqlc::QueryAnnotation;
+use crate::user_type::TypeMap;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// `PreparedQueries` generator holding one prepared statement per query
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresPreparedQueries {
+    db_crate: DbCrate,
+    visibility: Visibility,
+}
+
+impl PostgresPreparedQueries {
+    pub(crate) fn new(db_crate: DbCrate, visibility: Visibility) -> Self {
+        Self {
+            db_crate,
+            visibility,
+        }
+    }
+
+    pub(crate) fn generate(
+        &self,
+        queries: &[PostgresQuery],
+        type_map: &impl TypeMap,
+    ) -> TokenStream {
+        let statement_ident = self.db_crate.statement_ident();
+        let client_ident = self.db_crate.client_ident();
+        let error_ident = self.db_crate.error_ident();
+        let async_ident = self.db_crate.async_ident();
+        let visibility = self.visibility;
+
+        let fields = queries.iter().map(|query| {
+            let field_ident = query.query_func.ident();
+            quote! { #field_ident: #statement_ident }
+        });
+
+        let prepares = queries.iter().map(|query| {
+            let field_ident = query.query_func.ident();
+            let prepare = self.generate_prepare(query);
+            quote! { #field_ident: #prepare }
+        });
+
+        let methods = queries
+            .iter()
+            .map(|query| self.generate_method(query, type_map));
+
+        quote! {
+            /// Statements prepared once per connection
+            ///
+            /// Prepared statements belong to the connection that prepared them, so the
+            /// methods must be called with the same client that was passed to `prepare`.
+            #[derive(Debug, Clone)]
+            #visibility struct PreparedQueries {
+                #(#fields,)*
+            }
+
+            impl PreparedQueries {
+                pub #async_ident fn prepare(client: #client_ident) -> Result<Self, #error_ident> {
+                    Ok(Self {
+                        #(#prepares,)*
+                    })
+                }
+
+                #(#methods)*
+            }
+        }
+    }
+
+    /// Prepare with the catalog's parameter types
+    fn generate_prepare(&self, query: &PostgresQuery) -> TokenStream {
+        let query_ident = query.query_const.ident();
+        let await_def = self.db_crate.await_ident();
+
+        match (self.db_crate, query.query_params.params.is_empty()) {
+            (_, false) => query
+                .query_params
+                .to_prepare_typed(quote! { #query_ident }, self.db_crate),
+            (DbCrate::DeadPoolPostgres, true) => quote! {
+                client.prepare_cached(#query_ident)#await_def?
+            },
+            (_, true) => quote! {
+                client.prepare(#query_ident)#await_def?
+            },
+        }
+    }
+
+    fn generate_method(&self, query: &PostgresQuery, type_map: &impl TypeMap) -> TokenStream {
+        let method_ident = query.query_func.ident();
+        let client_ident = self.db_crate.client_ident();
+        let error_ident = self.db_crate.error_ident();
+        let async_ident = self.db_crate.async_ident();
+        let await_def = self.db_crate.await_ident();
+        let args = query.query_params.to_func_args(type_map);
+        let params = query.query_params.to_stmt_params();
+        let returning_ident = query.returning_row.return_type();
+        let row_ident = syn::Ident::new("row", proc_macro2::Span::call_site());
+        let from_row = query.returning_row.decode_row(&row_ident);
+
+        match query.query_type {
+            QueryAnnotation::One => quote! {
+                pub #async_ident fn #method_ident(&self, client: #client_ident, #args) -> Result<Option<#returning_ident>, #error_ident> {
+                    let row = client.query_opt(&self.#method_ident, #params)#await_def?;
+                    match row {
+                        Some(row) => Ok(Some(#from_row?)),
+                        None => Ok(None),
+                    }
+                }
+            },
+            QueryAnnotation::Many => quote! {
+                pub #async_ident fn #method_ident(&self, client: #client_ident, #args) -> Result<impl Iterator<Item = Result<#returning_ident, #error_ident>>, #error_ident> {
+                    let rows = client.query(&self.#method_ident, #params)#await_def?;
+                    Ok(rows.into_iter().map(|row| #from_row))
+                }
+            },
+            _ => quote! {
+                pub #async_ident fn #method_ident(&self, client: #client_ident, #args) -> Result<u64, #error_ident> {
+                    client.execute(&self.#method_ident, #params)#await_def
+                }
+            },
+        }
+    }
+}
+
+/// Generates the private `lookup_type` helper resolving non built-in parameter types
+pub(crate) fn generate_lookup_type(db_crate: DbCrate) -> TokenStream {
+    let client_ident = db_crate.client_ident();
+    let error_ident = db_crate.error_ident();
+    let type_ident = db_crate.type_ident();
+    let async_ident = db_crate.async_ident();
+    let await_def = db_crate.await_ident();
+    let prepare_ident = match db_crate {
+        DbCrate::DeadPoolPostgres => quote! { prepare_cached },
+        DbCrate::TokioPostgres | DbCrate::Postgres => quote! { prepare },
+    };
+
+    quote! {
+        /// Looks up a database type such as an enum or composite by its quoted name
+        #async_ident fn lookup_type(client: #client_ident, type_name: &str) -> Result<#type_ident, #error_ident> {
+            let stmt = client.#prepare_ident(&format!("SELECT NULL::{}", type_name))#await_def?;
+            Ok(stmt.columns()[0].type_().clone())
+        }
+    }
+}
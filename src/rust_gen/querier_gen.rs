@@ -0,0 +1,391 @@
+use crate::db_support::DbCrate;
+use crate::directive::Visibility;
+use crate::query::PostgresQuery;
+use crate::rust_gen::naming::RustSelfIdent;
+use crate::sqlc::QueryAnnotation;
+use crate::user_type::TypeMap;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::Ident;
+use syn::ext::IdentExt;
+
+/// `Querier` trait generator with an optional in-memory `MockQuerier`
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresQuerierGen {
+    db_crate: DbCrate,
+    emit_mock: bool,
+    /// `#[cfg(...)]` attribute attached to every mock item
+    mock_cfg: TokenStream,
+    visibility: Visibility,
+}
+
+impl PostgresQuerierGen {
+    pub(crate) fn new(
+        db_crate: DbCrate,
+        emit_mock: bool,
+        mock_cfg: Option<&str>,
+        visibility: Visibility,
+    ) -> crate::Result<Self> {
+        let mock_cfg = match mock_cfg {
+            Some(predicate) => {
+                let predicate = predicate
+                    .parse::<TokenStream>()
+                    .map_err(|_| crate::Error::invalid_option("mock_querier_cfg", predicate))?;
+                quote! { #[cfg(#predicate)] }
+            }
+            None => quote! {},
+        };
+        Ok(Self {
+            db_crate,
+            emit_mock,
+            mock_cfg,
+            visibility,
+        })
+    }
+
+    pub(crate) fn generate(
+        &self,
+        queries: &[PostgresQuery],
+        type_map: &impl TypeMap,
+    ) -> TokenStream {
+        let querier_trait = self.generate_trait(queries, type_map);
+        let client_impl = self.generate_client_impl(queries, type_map);
+        let mock = if self.emit_mock {
+            self.generate_mock(queries, type_map)
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            #querier_trait
+            #client_impl
+            #mock
+        }
+    }
+
+    /// Value returned by a `Querier` method on success
+    fn output_type(&self, query: &PostgresQuery) -> TokenStream {
+        let returning_ident = query.returning_row.return_type();
+        match query.query_type {
+            QueryAnnotation::One => quote! { Option<#returning_ident> },
+            QueryAnnotation::Many => quote! { Vec<#returning_ident> },
+            _ => quote! { u64 },
+        }
+    }
+
+    fn method_sig(
+        &self,
+        query: &PostgresQuery,
+        type_map: &impl TypeMap,
+        error_ident: &TokenStream,
+    ) -> TokenStream {
+        let method_ident = query.query_func.ident();
+        let receiver = self.db_crate.receiver_ident();
+        let args = query.query_params.to_func_args(type_map);
+        let async_ident = self.db_crate.async_ident();
+        let output = self.output_type(query);
+
+        quote! {
+            #async_ident fn #method_ident(#receiver, #args) -> Result<#output, #error_ident>
+        }
+    }
+
+    fn generate_trait(&self, queries: &[PostgresQuery], type_map: &impl TypeMap) -> TokenStream {
+        let methods = queries.iter().map(|query| {
+            let method_ident = query.query_func.ident();
+            let receiver = self.db_crate.receiver_ident();
+            let args = query.query_params.to_func_args(type_map);
+            let output = self.output_type(query);
+
+            match self.db_crate {
+                DbCrate::TokioPostgres | DbCrate::DeadPoolPostgres => quote! {
+                    fn #method_ident(#receiver, #args) -> impl std::future::Future<Output = Result<#output, Self::Error>> + Send;
+                },
+                DbCrate::Postgres => quote! {
+                    fn #method_ident(#receiver, #args) -> Result<#output, Self::Error>;
+                },
+            }
+        });
+
+        let visibility = self.visibility;
+        quote! {
+            #visibility trait Querier {
+                type Error;
+                #(#methods)*
+            }
+        }
+    }
+
+    /// Blanket implementation forwarding to the generated query functions
+    fn generate_client_impl(
+        &self,
+        queries: &[PostgresQuery],
+        type_map: &impl TypeMap,
+    ) -> TokenStream {
+        let generic_client = self.db_crate.generic_client_ident();
+        let error_ident = self.db_crate.error_ident();
+        let await_def = self.db_crate.await_ident();
+        let self_error = quote! { Self::Error };
+
+        let methods = queries.iter().map(|query| {
+            let sig = self.method_sig(query, type_map, &self_error);
+            let func_ident = query.query_func.ident();
+            let arg_idents = param_idents(query);
+            let call = quote! { #func_ident(self, #(#arg_idents),*)#await_def };
+
+            let body = match query.query_type {
+                QueryAnnotation::Many => quote! { #call?.collect() },
+                _ => call,
+            };
+
+            quote! {
+                #sig {
+                    #body
+                }
+            }
+        });
+
+        let client_bound = match self.db_crate {
+            DbCrate::TokioPostgres | DbCrate::DeadPoolPostgres => quote! { #generic_client + Sync },
+            DbCrate::Postgres => quote! { #generic_client },
+        };
+
+        // Deprecated queries stay callable through the interface
+        quote! {
+            #[allow(deprecated)]
+            impl<C: #client_bound> Querier for C {
+                type Error = #error_ident;
+                #(#methods)*
+            }
+        }
+    }
+
+    fn generate_mock(&self, queries: &[PostgresQuery], type_map: &impl TypeMap) -> TokenStream {
+        let mock_cfg = &self.mock_cfg;
+        let mock_error = quote! { MockQuerierError };
+        let visibility = self.visibility;
+
+        let call_variants = queries.iter().map(|query| {
+            let variant = query.struct_api.query_struct_ident();
+            if query.query_params.params.is_empty() {
+                quote! { #variant }
+            } else if query
+                .struct_api
+                .needs_lifetime(&query.query_params, type_map)
+            {
+                quote! { #variant(#variant<'static>) }
+            } else {
+                quote! { #variant(#variant) }
+            }
+        });
+
+        let queue_fields = queries.iter().map(|query| {
+            let field_ident = query.query_func.ident();
+            let output = self.output_type(query);
+            quote! {
+                #field_ident: std::sync::Mutex<std::collections::VecDeque<Result<#output, #mock_error>>>
+            }
+        });
+
+        let push_methods = queries.iter().map(|query| {
+            let field_ident = query.query_func.ident();
+            let method_ident = format_ident!("push_{}", field_ident.unraw());
+            let output = self.output_type(query);
+            quote! {
+                pub fn #method_ident(&self, result: Result<#output, #mock_error>) -> &Self {
+                    self.#field_ident.lock().unwrap().push_back(result);
+                    self
+                }
+            }
+        });
+
+        let self_error = quote! { Self::Error };
+        let querier_methods = queries.iter().map(|query| {
+            let sig = self.method_sig(query, type_map, &self_error);
+            let field_ident = query.query_func.ident();
+            let record = self.generate_mock_call(query, type_map);
+            let missing = format!(
+                "MockQuerier: no queued result for `{}`",
+                field_ident.unraw()
+            );
+
+            quote! {
+                #sig {
+                    self.calls.lock().unwrap().push(#record);
+                    self.#field_ident
+                        .lock()
+                        .unwrap()
+                        .pop_front()
+                        .expect(#missing)
+                }
+            }
+        });
+
+        let call_impls = self.generate_mock_call_impls(queries, type_map);
+
+        quote! {
+            #mock_cfg
+            #[derive(Debug)]
+            #visibility enum MockQuerierCall {
+                #(#call_variants,)*
+            }
+
+            #call_impls
+
+            #mock_cfg
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            #visibility struct MockQuerierError(pub String);
+
+            #mock_cfg
+            impl std::fmt::Display for MockQuerierError {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(&self.0)
+                }
+            }
+
+            #mock_cfg
+            impl std::error::Error for MockQuerierError {}
+
+            #mock_cfg
+            #[derive(Debug, Default)]
+            #visibility struct MockQuerier {
+                calls: std::sync::Mutex<Vec<MockQuerierCall>>,
+                #(#queue_fields,)*
+            }
+
+            #mock_cfg
+            impl MockQuerier {
+                pub fn new() -> Self {
+                    Self::default()
+                }
+
+                #(#push_methods)*
+
+                /// Returns the recorded calls in order and clears the log
+                pub fn take_calls(&self) -> Vec<MockQuerierCall> {
+                    std::mem::take(&mut *self.calls.lock().unwrap())
+                }
+            }
+
+            #mock_cfg
+            impl Querier for MockQuerier {
+                type Error = #mock_error;
+                #(#querier_methods)*
+            }
+        }
+    }
+
+    /// `Clone` of `MockQuerierCall`, and `PartialEq` when every parameter type implements it
+    ///
+    /// They are implemented by hand so the query structs need not derive them.
+    fn generate_mock_call_impls(
+        &self,
+        queries: &[PostgresQuery],
+        type_map: &impl TypeMap,
+    ) -> TokenStream {
+        let mock_cfg = &self.mock_cfg;
+        let partial_eq = queries
+            .iter()
+            .flat_map(|query| query.query_params.params.iter())
+            .all(|param| type_map.is_partial_eq_type(&param.inner.rs_type.to_string()));
+        if queries.is_empty() {
+            return quote! {
+                #mock_cfg
+                impl Clone for MockQuerierCall {
+                    fn clone(&self) -> Self {
+                        match *self {}
+                    }
+                }
+            };
+        }
+
+        let mut clone_arms = Vec::with_capacity(queries.len());
+        let mut eq_arms = Vec::with_capacity(queries.len());
+        for query in queries {
+            let variant = query.struct_api.query_struct_ident();
+            if query.query_params.params.is_empty() {
+                clone_arms.push(quote! { Self::#variant => Self::#variant });
+                eq_arms.push(quote! { (Self::#variant, Self::#variant) => true });
+                continue;
+            }
+            let fields = query
+                .query_params
+                .params
+                .iter()
+                .map(|param| crate::utils::rust_ident(&param.inner.name))
+                .collect::<Vec<_>>();
+            clone_arms.push(quote! {
+                Self::#variant(call) => Self::#variant(#variant {
+                    #(#fields: call.#fields.clone(),)*
+                })
+            });
+            eq_arms.push(quote! {
+                (Self::#variant(call), Self::#variant(other)) => #(call.#fields == other.#fields)&&*
+            });
+        }
+        let other_arm = (queries.len() > 1).then(|| quote! { _ => false, });
+
+        let partial_eq_impl = partial_eq.then(|| {
+            quote! {
+                #mock_cfg
+                impl PartialEq for MockQuerierCall {
+                    fn eq(&self, other: &Self) -> bool {
+                        match (self, other) {
+                            #(#eq_arms,)*
+                            #other_arm
+                        }
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #mock_cfg
+            impl Clone for MockQuerierCall {
+                fn clone(&self) -> Self {
+                    match self {
+                        #(#clone_arms,)*
+                    }
+                }
+            }
+
+            #partial_eq_impl
+        }
+    }
+
+    /// Build the `MockQuerierCall` value for a call, owning all borrowed params
+    fn generate_mock_call(&self, query: &PostgresQuery, type_map: &impl TypeMap) -> TokenStream {
+        let variant = query.struct_api.query_struct_ident();
+        if query.query_params.params.is_empty() {
+            return quote! { MockQuerierCall::#variant };
+        }
+
+        let fields = query.query_params.params.iter().map(|param| {
+            let field_ident = crate::utils::rust_ident(&param.inner.name);
+            if param.is_copy_cheap_type(type_map) {
+                quote! { #field_ident: #field_ident }
+            } else if param.inner.is_nullable {
+                quote! {
+                    #field_ident: #field_ident.map(|v| std::borrow::Cow::Owned(v.to_owned()))
+                }
+            } else {
+                quote! { #field_ident: std::borrow::Cow::Owned(#field_ident.to_owned()) }
+            }
+        });
+
+        quote! {
+            MockQuerierCall::#variant(#variant {
+                #(#fields,)*
+            })
+        }
+    }
+}
+
+fn param_idents(query: &PostgresQuery) -> Vec<Ident> {
+    query
+        .query_params
+        .params
+        .iter()
+        .map(|p| crate::utils::rust_ident(&p.inner.name))
+        .collect()
+}
@@ -0,0 +1,121 @@
+use crate::db_support::DbCrate;
+use crate::directive::Visibility;
+use crate::query::PostgresQuery;
+use crate::rust_gen::naming::RustSelfIdent;
+use crate::sqlc::QueryAnnotation;
+use crate::user_type::TypeMap;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Generates the `PgQuery` trait implemented by every query struct
+pub(crate) fn generate_query_trait(db_crate: DbCrate, visibility: Visibility) -> TokenStream {
+    let tosql_ident = db_crate.tosql_ident();
+    let row_ident = db_crate.row_ident();
+    let error_ident = db_crate.error_ident();
+
+    quote! {
+        /// Statement of a generated query struct, for code generic over queries
+        #visibility trait PgQuery {
+            /// Query name in the SQL file
+            const NAME: &'static str;
+            const SQL: &'static str;
+            /// Annotation such as `:one`
+            const ANNOTATION: &'static str;
+            /// Value decoded from each returned row, `()` for queries returning no rows
+            type Row;
+
+            /// Parameters in statement order, to pass to the client as `&params`
+            fn params(&self) -> Vec<&(dyn #tosql_ident + Sync)>;
+
+            fn decode_row(row: &#row_ident) -> Result<Self::Row, #error_ident>;
+        }
+    }
+}
+
+/// Implements `PgQuery` for the query struct of `query`
+pub(crate) fn generate_query_trait_impl(
+    db_crate: DbCrate,
+    query: &PostgresQuery,
+    type_map: &impl TypeMap,
+) -> TokenStream {
+    let struct_ident = query.struct_api.query_struct_ident();
+    let lifetime_param = if query
+        .struct_api
+        .needs_lifetime(&query.query_params, type_map)
+    {
+        quote! { <'a> }
+    } else {
+        quote! {}
+    };
+    let tosql_ident = db_crate.tosql_ident();
+    let row_ident = db_crate.row_ident();
+    let error_ident = db_crate.error_ident();
+    let name = query.query_const.name();
+    let query_ident = query.query_const.ident();
+    let annotation = query.query_type.to_string();
+
+    // `Cow` fields are matched so both variants lend a `ToSql` value stored in `self`,
+    // the `&T` of a borrowed one or the `String`, `Vec<T>` or `T` of an owned one
+    let params = query.query_params.params.iter().map(|param| {
+        let field_ident = crate::utils::rust_ident(&param.inner.name);
+        if param.is_copy_cheap_type(type_map) {
+            return quote! { &self.#field_ident };
+        }
+        let base_type = param.wrap_type();
+        let cow = quote! { std::borrow::Cow };
+        if param.inner.is_nullable {
+            quote! {
+                match &self.#field_ident {
+                    Some(#cow::Borrowed(param)) => param,
+                    Some(#cow::Owned(param)) => param,
+                    None => &None::<&#base_type>,
+                }
+            }
+        } else {
+            quote! {
+                match &self.#field_ident {
+                    #cow::Borrowed(param) => param,
+                    #cow::Owned(param) => param,
+                }
+            }
+        }
+    });
+
+    let (row_type, decode_row) = match query.query_type {
+        QueryAnnotation::One | QueryAnnotation::Many => {
+            let row = syn::Ident::new("row", proc_macro2::Span::call_site());
+            let decode = query.returning_row.decode_row(&row);
+            (
+                query.returning_row.return_type(),
+                quote! {
+                    fn decode_row(row: &#row_ident) -> Result<Self::Row, #error_ident> {
+                        #decode
+                    }
+                },
+            )
+        }
+        _ => (
+            quote! { () },
+            quote! {
+                fn decode_row(_row: &#row_ident) -> Result<Self::Row, #error_ident> {
+                    Ok(())
+                }
+            },
+        ),
+    };
+
+    quote! {
+        impl #lifetime_param PgQuery for #struct_ident #lifetime_param {
+            const NAME: &'static str = #name;
+            const SQL: &'static str = #query_ident;
+            const ANNOTATION: &'static str = #annotation;
+            type Row = #row_type;
+
+            fn params(&self) -> Vec<&(dyn #tosql_ident + Sync)> {
+                vec![#(#params),*]
+            }
+
+            #decode_row
+        }
+    }
+}
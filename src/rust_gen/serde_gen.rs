@@ -0,0 +1,106 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::{Deserialize, Deserializer};
+
+/// `rename_all` rule of the serde attributes on row and query structs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RenameAll {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameAll {
+    fn as_str(self) -> &'static str {
+        match self {
+            RenameAll::Lower => "lowercase",
+            RenameAll::Upper => "UPPERCASE",
+            RenameAll::Pascal => "PascalCase",
+            RenameAll::Camel => "camelCase",
+            RenameAll::Snake => "snake_case",
+            RenameAll::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+            RenameAll::Kebab => "kebab-case",
+            RenameAll::ScreamingKebab => "SCREAMING-KEBAB-CASE",
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RenameAll {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        const RULES: &[RenameAll] = &[
+            RenameAll::Lower,
+            RenameAll::Upper,
+            RenameAll::Pascal,
+            RenameAll::Camel,
+            RenameAll::Snake,
+            RenameAll::ScreamingSnake,
+            RenameAll::Kebab,
+            RenameAll::ScreamingKebab,
+        ];
+        let s = String::deserialize(deserializer)?;
+        RULES
+            .iter()
+            .find(|rule| rule.as_str() == s)
+            .copied()
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown rename_all: {}", s)))
+    }
+}
+
+/// `serde` support of the generated types
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct SerdeOptions {
+    pub(crate) enabled: bool,
+    pub(crate) rename_all: Option<RenameAll>,
+}
+
+impl SerdeOptions {
+    /// Derives and `rename_all` of row and query structs
+    ///
+    /// `Cow` fields of query structs are not borrowed, so the structs are
+    /// `DeserializeOwned` and can be read by web framework extractors.
+    pub(crate) fn struct_attrs(&self) -> TokenStream {
+        if !self.enabled {
+            return quote! {};
+        }
+        let rename_all = self.rename_all.map(|rule| {
+            let rule = rule.as_str();
+            quote! { #[serde(rename_all = #rule)] }
+        });
+        quote! {
+            #[derive(serde::Serialize, serde::Deserialize)]
+            #rename_all
+        }
+    }
+
+    /// Derives of enums, whose variants are renamed to the DB labels
+    pub(crate) fn enum_attrs(&self) -> TokenStream {
+        if !self.enabled {
+            return quote! {};
+        }
+        quote! { #[derive(serde::Serialize, serde::Deserialize)] }
+    }
+
+    /// Serializes an enum variant as its DB label
+    pub(crate) fn variant_attr(&self, label: &str) -> TokenStream {
+        if !self.enabled {
+            return quote! {};
+        }
+        quote! { #[serde(rename = #label)] }
+    }
+
+    /// Serializes the `Unknown(String)` variant as the label it holds
+    pub(crate) fn unknown_variant_attr(&self) -> TokenStream {
+        if !self.enabled {
+            return quote! {};
+        }
+        quote! { #[serde(untagged)] }
+    }
+}
@@ -1,9 +1,15 @@
 use crate::db_support::DbCrate;
+use crate::directive::Visibility;
+use crate::query::QueryGenOptions;
+use crate::rust_gen::func_gen::{generate_row_stream, stream_sig};
+use crate::rust_gen::instrument_gen::{QuerySpan, RowCount};
 use crate::rust_gen::naming::RustSelfIdent;
 use crate::rust_gen::param_gen::PgParams;
 use crate::rust_gen::struct_gen::PgStruct;
+use crate::rust_gen::timeout_gen::wrap_timeout;
 use crate::sqlc::QueryAnnotation;
 use crate::user_type::TypeMap;
+use crate::utils::Renames;
 use proc_macro2::TokenStream;
 use quote::quote;
 
@@ -13,6 +19,10 @@ pub(crate) struct PostgresStructApi {
     query_name: String,
     annotation: QueryAnnotation,
     db_crate: DbCrate,
+    options: QueryGenOptions,
+    span: QuerySpan,
+    /// Attributes of the query struct from the `attributes` option
+    attrs: TokenStream,
 }
 
 impl PostgresStructApi {
@@ -20,12 +30,18 @@ impl PostgresStructApi {
         query: &crate::plugin::Query,
         annotation: QueryAnnotation,
         db_crate: DbCrate,
+        options: QueryGenOptions,
+        attrs: TokenStream,
+        renames: &Renames,
     ) -> Self {
-        let query_name = crate::utils::rust_value_ident(&query.name);
+        let query_name = crate::utils::rust_value_ident(&query.name, renames);
         Self {
             query_name,
             annotation,
             db_crate,
+            options,
+            span: QuerySpan::new(query),
+            attrs,
         }
     }
 
@@ -48,7 +64,7 @@ impl PostgresStructApi {
         // Generate struct fields with optimized types
         for param in &query_params.params {
             let field_name = &param.inner.name;
-            let field_ident = syn::Ident::new(field_name, proc_macro2::Span::call_site());
+            let field_ident = crate::utils::rust_ident(field_name);
 
             if param.is_copy_cheap_type(type_map) {
                 // Copy-cheap types: pass by value
@@ -83,14 +99,77 @@ impl PostgresStructApi {
             quote! {}
         };
 
+        let visibility = self.options.visibility;
+        let serde_attrs = self.options.serde.struct_attrs();
+        let attrs = &self.attrs;
+        let (clone_derive, into_owned) = if self.options.emit_owned_queries {
+            let into_owned = has_lifetime.then(|| self.generate_into_owned(query_params, type_map));
+            (quote! { #[derive(Clone)] }, into_owned)
+        } else {
+            (quote! {}, None)
+        };
         quote! {
             #[derive(Debug)]
-            pub struct #struct_ident #lifetime_param {
+            #clone_derive
+            #serde_attrs
+            #attrs
+            #visibility struct #struct_ident #lifetime_param {
                 #field_tokens
             }
 
             impl #lifetime_param #struct_ident #lifetime_param {
                 #query_const_tokens
+                #into_owned
+            }
+        }
+    }
+
+    /// Generate a unit query struct for a query without parameters, so it can implement `PgQuery`
+    pub(crate) fn generate_unit_query_struct(&self) -> TokenStream {
+        if !self.options.emit_query_trait {
+            return quote! {};
+        }
+
+        let struct_ident = self.query_struct_ident();
+        let visibility = self.options.visibility;
+        let serde_attrs = self.options.serde.struct_attrs();
+        let attrs = &self.attrs;
+        let clone_derive = if self.options.emit_owned_queries {
+            quote! { #[derive(Clone)] }
+        } else {
+            quote! {}
+        };
+        quote! {
+            #[derive(Debug)]
+            #clone_derive
+            #serde_attrs
+            #attrs
+            #visibility struct #struct_ident;
+        }
+    }
+
+    /// `into_owned` copying borrowed `Cow` fields so the query can outlive its inputs
+    fn generate_into_owned(&self, query_params: &PgParams, type_map: &impl TypeMap) -> TokenStream {
+        let struct_ident = self.query_struct_ident();
+        let fields = query_params.params.iter().map(|param| {
+            let field_ident = crate::utils::rust_ident(&param.inner.name);
+            if param.is_copy_cheap_type(type_map) {
+                quote! { #field_ident: self.#field_ident }
+            } else if param.inner.is_nullable {
+                quote! {
+                    #field_ident: self.#field_ident.map(|v| std::borrow::Cow::Owned(v.into_owned()))
+                }
+            } else {
+                quote! { #field_ident: std::borrow::Cow::Owned(self.#field_ident.into_owned()) }
+            }
+        });
+
+        quote! {
+            /// Converts into a query owning all its parameters, e.g. to move it into a spawned task
+            pub fn into_owned(self) -> #struct_ident<'static> {
+                #struct_ident {
+                    #(#fields,)*
+                }
             }
         }
     }
@@ -117,58 +196,126 @@ impl PostgresStructApi {
 
         let client_ident = self.db_crate.client_ident();
         let error_ident = self.db_crate.error_ident();
+        let async_ident = self.db_crate.async_ident();
         let await_def = self.db_crate.await_ident();
         let _query_ident = query_const.ident();
 
         // Generate parameter passing for SQL execution
         let params = self.generate_stmt_params(query_params, type_map);
+        let (prepare, stmt) = query_params.to_statement(
+            quote! { Self::QUERY },
+            self.db_crate,
+            self.options.prepare_typed,
+        );
 
         match self.annotation {
             QueryAnnotation::One => {
-                let returning_ident = returning_row.ident();
+                let returning_ident = returning_row.return_type();
                 let row_ident = syn::Ident::new("row", proc_macro2::Span::call_site());
+                let from_row = returning_row.decode_row(&row_ident);
+
+                let query_one_body = self.instrument(
+                    RowCount::One,
+                    quote! {
+                        #prepare
+                        let #row_ident = client.query_one(#stmt, #params)#await_def?;
+                        #from_row
+                    },
+                );
+                let query_opt_body = self.instrument(
+                    RowCount::Optional,
+                    quote! {
+                        #prepare
+                        let #row_ident = client.query_opt(#stmt, #params)#await_def?;
+                        match #row_ident {
+                            Some(#row_ident) => Ok(Some(#from_row?)),
+                            None => Ok(None),
+                        }
+                    },
+                );
 
                 quote! {
                     impl #lifetime_param #struct_ident #lifetime_param {
-                        pub async fn query_one(&self, client: #client_ident) -> Result<#returning_ident, #error_ident> {
-                            let #row_ident = client.query_one(Self::QUERY, #params)#await_def?;
-                            #returning_ident::from_row(&#row_ident)
+                        pub #async_ident fn query_one(&self, client: #client_ident) -> Result<#returning_ident, #error_ident> {
+                            #query_one_body
                         }
 
-                        pub async fn query_opt(&self, client: #client_ident) -> Result<Option<#returning_ident>, #error_ident> {
-                            let #row_ident = client.query_opt(Self::QUERY, #params)#await_def?;
-                            match #row_ident {
-                                Some(ref #row_ident) => Ok(Some(#returning_ident::from_row(#row_ident)?)),
-                                None => Ok(None),
-                            }
+                        pub #async_ident fn query_opt(&self, client: #client_ident) -> Result<Option<#returning_ident>, #error_ident> {
+                            #query_opt_body
                         }
                     }
                 }
             }
             QueryAnnotation::Many => {
-                let returning_ident = returning_row.ident();
+                let returning_ident = returning_row.return_type();
                 let rows_ident = syn::Ident::new("rows", proc_macro2::Span::call_site());
                 let row_ident = syn::Ident::new("r", proc_macro2::Span::call_site());
+                let from_row = returning_row.decode_row(&row_ident);
+
+                let query_stream = if self.options.emit_streaming {
+                    let sig = stream_sig(
+                        self.db_crate,
+                        Visibility::Public,
+                        &syn::Ident::new("query", proc_macro2::Span::call_site()),
+                        quote! { &self, },
+                        quote! {},
+                        returning_row,
+                    );
+                    let body = generate_row_stream(self.db_crate, &stmt, &params, returning_row);
+                    quote! {
+                        #sig {
+                            #prepare
+                            #body
+                        }
+                    }
+                } else {
+                    quote! {}
+                };
+
+                let query_many_body = self.instrument(
+                    RowCount::Len,
+                    quote! {
+                        #prepare
+                        let #rows_ident = client.query(#stmt, #params)#await_def?;
+                        #rows_ident.into_iter().map(|#row_ident| #from_row).collect::<Result<Vec<_>, _>>()
+                    },
+                );
+                let query_raw_body = self.instrument(
+                    RowCount::Len,
+                    quote! {
+                        #prepare
+                        let #rows_ident = client.query(#stmt, #params)#await_def?;
+                        Ok(#rows_ident.into_iter().map(|#row_ident| #from_row))
+                    },
+                );
 
                 quote! {
                     impl #lifetime_param #struct_ident #lifetime_param {
-                        pub async fn query_many(&self, client: #client_ident) -> Result<Vec<#returning_ident>, #error_ident> {
-                            let #rows_ident = client.query(Self::QUERY, #params)#await_def?;
-                            #rows_ident.into_iter().map(|#row_ident| #returning_ident::from_row(&#row_ident)).collect()
+                        pub #async_ident fn query_many(&self, client: #client_ident) -> Result<Vec<#returning_ident>, #error_ident> {
+                            #query_many_body
                         }
 
-                        pub async fn query_raw(&self, client: #client_ident) -> Result<impl Iterator<Item = Result<#returning_ident, #error_ident>>, #error_ident> {
-                            let #rows_ident = client.query(Self::QUERY, #params)#await_def?;
-                            Ok(#rows_ident.into_iter().map(|#row_ident| #returning_ident::from_row(&#row_ident)))
+                        pub #async_ident fn query_raw(&self, client: #client_ident) -> Result<impl Iterator<Item = Result<#returning_ident, #error_ident>>, #error_ident> {
+                            #query_raw_body
                         }
+
+                        #query_stream
                     }
                 }
             }
             QueryAnnotation::Exec => {
+                let execute_body = self.instrument(
+                    RowCount::Affected,
+                    quote! {
+                        #prepare
+                        client.execute(#stmt, #params)#await_def
+                    },
+                );
+
                 quote! {
                     impl #lifetime_param #struct_ident #lifetime_param {
-                        pub async fn execute(&self, client: #client_ident) -> Result<u64, #error_ident> {
-                            client.execute(Self::QUERY, #params)#await_def
+                        pub #async_ident fn execute(&self, client: #client_ident) -> Result<u64, #error_ident> {
+                            #execute_body
                         }
                     }
                 }
@@ -180,11 +327,23 @@ impl PostgresStructApi {
         }
     }
 
-    fn query_struct_ident(&self) -> syn::Ident {
-        syn::Ident::new(&self.query_name, proc_macro2::Span::call_site())
+    /// Wraps a method body with the configured timeout and instrumentation
+    fn instrument(&self, row_count: RowCount, body: TokenStream) -> TokenStream {
+        let body = wrap_timeout(self.db_crate, self.options.timeout, body);
+        self.span.wrap(
+            &self.options,
+            self.db_crate,
+            quote! { Self::QUERY },
+            row_count,
+            body,
+        )
+    }
+
+    pub(crate) fn query_struct_ident(&self) -> syn::Ident {
+        crate::utils::rust_ident(&self.query_name)
     }
 
-    fn needs_lifetime(&self, query_params: &PgParams, type_map: &impl TypeMap) -> bool {
+    pub(crate) fn needs_lifetime(&self, query_params: &PgParams, type_map: &impl TypeMap) -> bool {
         query_params
             .params
             .iter()
@@ -200,7 +359,7 @@ impl PostgresStructApi {
 
         for param in &query_params.params {
             let field_name = &param.inner.name;
-            let field_ident = syn::Ident::new(field_name, proc_macro2::Span::call_site());
+            let field_ident = crate::utils::rust_ident(field_name);
 
             if param.is_copy_cheap_type(type_map) {
                 // Copy-cheap types: pass by reference for SQL execution
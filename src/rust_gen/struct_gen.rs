@@ -1,19 +1,64 @@
 use crate::db_support::PgColumn;
+use crate::directive::Visibility;
+use crate::query::QueryGenOptions;
 use crate::rust_gen::naming::{
-    RustSelfIdent, column_name_from_list, generate_unique_field_names, has_single_table_identifier,
+    RustSelfIdent, check_escaped_field_names, column_name_from_list, generate_unique_field_names,
+    has_single_table_identifier,
 };
 use crate::user_type::TypeMap;
 use crate::{plugin, utils};
-use proc_macro2::{Literal, Span};
+use proc_macro2::Literal;
 use quote::{ToTokens, quote};
+use serde::{Deserialize, Deserializer};
 use syn::Ident;
 
+/// How `from_row` reads the columns of a row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum RowDecoding {
+    /// By position in the result set
+    #[default]
+    Index,
+    /// By column name
+    Name,
+}
+
+impl<'de> Deserialize<'de> for RowDecoding {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        match s.as_str() {
+            "index" => Ok(RowDecoding::Index),
+            "name" => Ok(RowDecoding::Name),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown row_decoding: {}",
+                s
+            ))),
+        }
+    }
+}
+
 /// PostgreSQL struct generator for query results
 #[derive(Debug, Clone)]
 pub(crate) struct PgStruct {
     pub(crate) name: String,
     pub(crate) columns: Vec<PgColumn>,
     pub(crate) db_crate: crate::db_support::DbCrate,
+    /// Comment of the table when every column comes from the same one
+    pub(crate) comment: String,
+    /// Return the single column directly instead of a row struct
+    pub(crate) scalar: bool,
+    pub(crate) visibility: Visibility,
+    /// Expose `from_row` outside of the crate
+    pub(crate) public_from_row: bool,
+    /// Implement `FromPgRow` and `TryFrom<&Row>`
+    pub(crate) emit_from_row_trait: bool,
+    pub(crate) row_decoding: RowDecoding,
+    /// Check the result columns against the catalog in debug builds
+    pub(crate) validate_columns: bool,
+    /// Emit a `XxxRowRef<'r>` borrowing text and `bytea` columns from the row
+    pub(crate) emit_row_ref: bool,
 }
 
 impl PgStruct {
@@ -21,8 +66,11 @@ impl PgStruct {
         query: &plugin::Query,
         pg_map: &impl TypeMap,
         db_crate: crate::db_support::DbCrate,
+        scalar: bool,
+        options: QueryGenOptions,
     ) -> crate::Result<Self> {
-        let is_single_table_identifier = has_single_table_identifier(query);
+        let renames = pg_map.renames();
+        let is_single_table_identifier = has_single_table_identifier(query, renames);
 
         // Generate unique field names to avoid conflicts
         let field_names = if is_single_table_identifier {
@@ -33,7 +81,7 @@ impl PgStruct {
                 .enumerate()
                 .map(|(idx, c)| {
                     if !c.name.is_empty() {
-                        crate::utils::rust_struct_field(&c.name)
+                        crate::utils::rust_struct_field(&c.name, renames)
                     } else {
                         format!("column_{}", idx)
                     }
@@ -41,8 +89,9 @@ impl PgStruct {
                 .collect()
         } else {
             // For multi-table, use unique name generation
-            generate_unique_field_names(query)
+            generate_unique_field_names(query, renames)
         };
+        check_escaped_field_names(&query.name, &field_names)?;
 
         let columns = query
             .columns
@@ -53,12 +102,31 @@ impl PgStruct {
             })
             .collect::<crate::Result<Vec<_>>>()?;
 
-        let name = utils::rust_value_ident(&query.name);
+        let mut tables = query
+            .columns
+            .iter()
+            .map(|c| c.table.as_ref().map(|t| t.name.as_str()));
+        let comment = match tables.next() {
+            Some(Some(table)) if tables.all(|t| t == Some(table)) => {
+                pg_map.table_comment(table).unwrap_or_default().to_string()
+            }
+            _ => String::new(),
+        };
+
+        let name = utils::rust_value_ident(&query.name, renames);
         let name = format!("{}Row", name);
         Ok(Self {
             name,
             columns,
             db_crate,
+            comment,
+            scalar,
+            visibility: options.visibility,
+            public_from_row: options.public_from_row,
+            emit_from_row_trait: options.emit_from_row_trait,
+            row_decoding: options.row_decoding,
+            validate_columns: options.validate_columns,
+            emit_row_ref: options.emit_row_refs,
         })
     }
 
@@ -66,7 +134,7 @@ impl PgStruct {
     pub(crate) fn to_from_row_expr(&self, var_ident: &Ident) -> proc_macro2::TokenStream {
         let mut st_inner = quote! {};
         for (idx, c) in self.columns.iter().enumerate() {
-            let field_ident = Ident::new(&c.name, Span::call_site());
+            let field_ident = utils::rust_ident(&c.name);
             let literal = Literal::usize_unsuffixed(idx);
             st_inner = quote! {
                 #st_inner
@@ -83,6 +151,42 @@ impl PgStruct {
     }
 }
 
+impl PgStruct {
+    /// `///` comments placed before the derives of the row struct
+    pub(crate) fn doc_attrs(&self) -> proc_macro2::TokenStream {
+        utils::doc_attrs(self.comment.lines())
+    }
+
+    /// Type returned for each row, the row struct or the scalar column type
+    pub(crate) fn return_type(&self) -> proc_macro2::TokenStream {
+        if self.scalar {
+            self.columns[0].field_type()
+        } else {
+            self.ident().to_token_stream()
+        }
+    }
+
+    /// Expression converting the `Row` or `&Row` named `row` into the return type
+    pub(crate) fn decode_row(&self, row: &Ident) -> proc_macro2::TokenStream {
+        if self.scalar {
+            quote! { #row.try_get(0) }
+        } else {
+            let ident = self.ident();
+            quote! { #ident::from_row(&#row) }
+        }
+    }
+
+    /// `fn(&Row) -> Result<T, Error>` converting a row into the return type
+    pub(crate) fn decode_row_fn(&self) -> proc_macro2::TokenStream {
+        if self.scalar {
+            quote! { |row| row.try_get(0) }
+        } else {
+            let ident = self.ident();
+            quote! { #ident::from_row }
+        }
+    }
+}
+
 impl RustSelfIdent for PgStruct {
     fn ident_str(&self) -> String {
         self.name.clone()
@@ -91,22 +195,28 @@ impl RustSelfIdent for PgStruct {
 
 impl ToTokens for PgStruct {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        if self.columns.is_empty() {
+        if self.columns.is_empty() || self.scalar {
             return;
         }
 
         let ident = self.ident();
         let columns = &self.columns;
         let from_row_method = self.generate_from_row_method();
+        let from_row_impls = self.generate_from_row_impls();
+        let row_ref = self.generate_row_ref();
+        let visibility = self.visibility;
 
         tokens.extend(quote! {
-            pub struct #ident {
+            #visibility struct #ident {
                 #(#columns),*
             }
 
             impl #ident {
                 #from_row_method
             }
+
+            #from_row_impls
+            #row_ref
         });
     }
 }
@@ -114,25 +224,267 @@ impl ToTokens for PgStruct {
 impl PgStruct {
     /// Generate a private from_row method to reduce code duplication
     fn generate_from_row_method(&self) -> proc_macro2::TokenStream {
+        let field_assignments = self.generate_field_assignments();
+        let validation = self.generate_column_validation();
+
+        let ident = self.ident();
+        let row_type = self.db_crate.row_ident();
+        let error_type = self.db_crate.error_ident();
+        let visibility = self.decode_visibility();
+
+        quote! {
+            #visibility fn from_row(row: &#row_type) -> Result<Self, #error_type> {
+                #validation
+                Ok(#ident {
+                    #field_assignments
+                })
+            }
+        }
+    }
+
+    /// Visibility of the `from_row` functions
+    fn decode_visibility(&self) -> proc_macro2::TokenStream {
+        if self.public_from_row {
+            quote! { pub }
+        } else {
+            quote! { pub(crate) }
+        }
+    }
+
+    /// `field: row.try_get(index)?,` for every column
+    fn generate_field_assignments(&self) -> proc_macro2::TokenStream {
         let mut field_assignments = quote! {};
         for (idx, c) in self.columns.iter().enumerate() {
-            let field_ident = Ident::new(&c.name, Span::call_site());
-            let literal = Literal::usize_unsuffixed(idx);
+            let field_ident = utils::rust_ident(&c.name);
+            let index = self.column_index(idx);
             field_assignments.extend(quote! {
-                #field_ident: row.try_get(#literal)?,
+                #field_ident: row.try_get(#index)?,
             });
         }
+        field_assignments
+    }
+
+    /// Borrowed variant of the row struct with a `to_owned` into the owned one
+    ///
+    /// Rows without text or `bytea` columns have nothing to borrow and get none.
+    fn generate_row_ref(&self) -> proc_macro2::TokenStream {
+        if !self.emit_row_ref
+            || self
+                .columns
+                .iter()
+                .all(|c| c.borrowed_field_type().is_none())
+        {
+            return quote! {};
+        }
+
+        let ident = self.ident();
+        let ref_ident = self.ref_ident();
+        let visibility = self.visibility;
+        let fields = self.columns.iter().map(|c| {
+            let field_ident = utils::rust_ident(&c.name);
+            let ty = c.borrowed_field_type().unwrap_or_else(|| c.field_type());
+            let docs = utils::doc_attrs(c.comment.lines());
+            quote! {
+                #docs
+                pub #field_ident: #ty
+            }
+        });
+        let field_idents = self
+            .columns
+            .iter()
+            .map(|c| utils::rust_ident(&c.name))
+            .collect::<Vec<_>>();
+        let owned_fields = self.columns.iter().map(|c| {
+            let field_ident = utils::rust_ident(&c.name);
+            match (c.borrowed_field_type(), c.is_nullable) {
+                (None, _) => quote! { #field_ident },
+                (Some(_), false) => quote! { #field_ident: #field_ident.to_owned() },
+                (Some(_), true) => {
+                    quote! { #field_ident: #field_ident.map(ToOwned::to_owned) }
+                }
+            }
+        });
+
+        let field_assignments = self.generate_field_assignments();
+        let validation = self.generate_column_validation();
+        let row_type = self.db_crate.row_ident();
+        let error_type = self.db_crate.error_ident();
+        let decode_visibility = self.decode_visibility();
+
+        quote! {
+            #[derive(Debug, Clone)]
+            #visibility struct #ref_ident<'r> {
+                #(#fields),*
+            }
+
+            impl<'r> #ref_ident<'r> {
+                #decode_visibility fn from_row(row: &'r #row_type) -> Result<Self, #error_type> {
+                    #validation
+                    Ok(#ref_ident {
+                        #field_assignments
+                    })
+                }
+
+                /// Copies the borrowed values into an owned row
+                pub fn to_owned(&self) -> #ident {
+                    let Self { #(#field_idents),* } = self.clone();
+                    #ident {
+                        #(#owned_fields),*
+                    }
+                }
+            }
+        }
+    }
+
+    /// Identifier of the borrowed row struct
+    fn ref_ident(&self) -> Ident {
+        utils::rust_ident(&format!("{}Ref", self.name))
+    }
+
+    /// Index passed to `try_get` for the column at `idx`
+    ///
+    /// Columns without a name or sharing it with another column, as in joins,
+    /// are always read by position.
+    fn column_index(&self, idx: usize) -> proc_macro2::TokenStream {
+        if self.column_index_is_name(idx) {
+            Literal::string(&self.columns[idx].column_name).to_token_stream()
+        } else {
+            Literal::usize_unsuffixed(idx).to_token_stream()
+        }
+    }
+
+    /// Whether the column at `idx` is read by its name rather than its position
+    fn column_index_is_name(&self, idx: usize) -> bool {
+        let name = &self.columns[idx].column_name;
+        self.row_decoding == RowDecoding::Name
+            && !name.is_empty()
+            && self
+                .columns
+                .iter()
+                .filter(|c| &c.column_name == name)
+                .count()
+                == 1
+    }
+
+    /// Debug assertions checking the names and types of the result columns
+    ///
+    /// When every column is read by name the order is free, so only the set of names is compared.
+    fn generate_column_validation(&self) -> proc_macro2::TokenStream {
+        if !self.validate_columns {
+            return quote! {};
+        }
+
+        let mut names = self
+            .columns
+            .iter()
+            .map(|c| c.column_name.as_str())
+            .collect::<Vec<_>>();
+        // unnamed columns leave only the count to check
+        let shape_check = if names.iter().all(|name| !name.is_empty()) {
+            let message = format!(
+                "columns of {} changed since the code was generated",
+                self.name
+            );
+            // columns all read by name may come in any order
+            if (0..names.len()).all(|idx| self.column_index_is_name(idx)) {
+                names.sort_unstable();
+                quote! {
+                    let mut names = columns.iter().map(|c| c.name()).collect::<Vec<_>>();
+                    names.sort_unstable();
+                    debug_assert_eq!(names, [#(#names),*], #message);
+                }
+            } else {
+                quote! {
+                    let names = columns.iter().map(|c| c.name()).collect::<Vec<_>>();
+                    debug_assert_eq!(names, [#(#names),*], #message);
+                }
+            }
+        } else {
+            let count = Literal::usize_unsuffixed(names.len());
+            let message = format!(
+                "column count of {} changed since the code was generated",
+                self.name
+            );
+            quote! { debug_assert_eq!(columns.len(), #count, #message); }
+        };
+
+        // `accepts` of the field type also covers enums, composites and overrides
+        let fromsql_ident = self.db_crate.fromsql_ident();
+        let accepts = self.columns.iter().enumerate().map(|(idx, c)| {
+            let field_type = c.field_type();
+            let index = self.column_index(idx);
+            if self.column_index_is_name(idx) {
+                quote! {
+                    columns
+                        .iter()
+                        .position(|c| c.name() == #index)
+                        .is_some_and(|idx| <#field_type as #fromsql_ident>::accepts(columns[idx].type_()))
+                }
+            } else {
+                quote! { <#field_type as #fromsql_ident>::accepts(columns[#index].type_()) }
+            }
+        });
+        let message = format!(
+            "type of column {{}} of {} changed since the code was generated",
+            self.name
+        );
+        let type_check = quote! {
+            let accepted = [#(#accepts),*];
+            for (idx, accepted) in accepted.into_iter().enumerate() {
+                debug_assert!(accepted, #message, idx);
+            }
+        };
+
+        quote! {
+            #[cfg(debug_assertions)]
+            {
+                let columns = row.columns();
+                #shape_check
+                #type_check
+            }
+        }
+    }
+
+    /// `FromPgRow` and `TryFrom<&Row>` forwarding to `from_row`
+    fn generate_from_row_impls(&self) -> proc_macro2::TokenStream {
+        if !self.emit_from_row_trait {
+            return quote! {};
+        }
 
         let ident = self.ident();
         let row_type = self.db_crate.row_ident();
         let error_type = self.db_crate.error_ident();
 
         quote! {
-            pub(crate) fn from_row(row: &#row_type) -> Result<Self, #error_type> {
-                Ok(#ident {
-                    #field_assignments
-                })
+            impl FromPgRow for #ident {
+                fn from_pg_row(row: &#row_type) -> Result<Self, #error_type> {
+                    Self::from_row(row)
+                }
+            }
+
+            impl TryFrom<&#row_type> for #ident {
+                type Error = #error_type;
+
+                fn try_from(row: &#row_type) -> Result<Self, Self::Error> {
+                    Self::from_row(row)
+                }
             }
         }
     }
 }
+
+/// Generates the `FromPgRow` trait implemented by every row struct
+pub(crate) fn generate_from_pg_row_trait(
+    db_crate: crate::db_support::DbCrate,
+    visibility: Visibility,
+) -> proc_macro2::TokenStream {
+    let row_type = db_crate.row_ident();
+    let error_type = db_crate.error_ident();
+
+    quote! {
+        /// Conversion of a `Row` into a generated row struct
+        #visibility trait FromPgRow: Sized {
+            fn from_pg_row(row: &#row_type) -> Result<Self, #error_type>;
+        }
+    }
+}
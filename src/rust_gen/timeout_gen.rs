@@ -0,0 +1,123 @@
+use crate::db_support::DbCrate;
+use crate::error::Error;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// Parses a duration such as `500ms`, `2s` or `1m` into milliseconds
+///
+/// A bare number is read as milliseconds like `statement_timeout`.
+/// Zero disables the timeout.
+pub(crate) fn parse_timeout(value: &str) -> Option<Option<u64>> {
+    let value = value.trim();
+    let digits = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(digits);
+    let amount = amount.parse::<u64>().ok()?;
+    let millis = match unit.trim() {
+        "" | "ms" => amount,
+        "s" => amount.checked_mul(1_000)?,
+        "m" | "min" => amount.checked_mul(60_000)?,
+        _ => return None,
+    };
+    Some((millis > 0).then_some(millis))
+}
+
+/// Rejects a timeout that `db_crate` cannot enforce
+pub(crate) fn check_timeout_support(
+    db_crate: DbCrate,
+    timeout_millis: Option<u64>,
+    source: &str,
+) -> Result<(), Error> {
+    if db_crate == DbCrate::DeadPoolPostgres && timeout_millis.is_some() {
+        return Err(Error::any_error(format!(
+            "{} is not supported with deadpool_postgres",
+            source
+        )));
+    }
+    Ok(())
+}
+
+/// Enforces `timeout_millis` around `body`, which must evaluate to the function's `Result`
+///
+/// `tokio_postgres` cancels the running query through its `CancelToken` and returns the
+/// server's `query_canceled` error. `postgres` sets the session `statement_timeout` in the
+/// same round trip that reads its previous value, and restores that value after the query.
+/// `deadpool_postgres` exposes
+/// neither the cancel token nor a transaction on a shared client, so its timeouts are
+/// rejected by [`check_timeout_support`].
+pub(crate) fn wrap_timeout(
+    db_crate: DbCrate,
+    timeout_millis: Option<u64>,
+    body: TokenStream,
+) -> TokenStream {
+    let Some(timeout_millis) = timeout_millis else {
+        return body;
+    };
+
+    match db_crate {
+        DbCrate::TokioPostgres => {
+            let timeout_millis = proc_macro2::Literal::u64_unsuffixed(timeout_millis);
+            quote! {
+                let cancel_token = client.client().cancel_token();
+                let mut query = std::pin::pin!(async { #body });
+                match tokio::time::timeout(std::time::Duration::from_millis(#timeout_millis), &mut query).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        cancel_token.cancel_query(tokio_postgres::NoTls).await?;
+                        query.await
+                    }
+                }
+            }
+        }
+        DbCrate::DeadPoolPostgres => {
+            unreachable!("query timeouts are rejected for deadpool_postgres")
+        }
+        DbCrate::Postgres => {
+            let set_timeout = format!(
+                "SHOW statement_timeout; SET statement_timeout = {}",
+                timeout_millis
+            );
+            quote! {
+                let messages = client.simple_query(#set_timeout)?;
+                let previous = messages
+                    .iter()
+                    .find_map(|message| match message {
+                        postgres::SimpleQueryMessage::Row(row) => row.get(0),
+                        _ => None,
+                    })
+                    .unwrap_or("0")
+                    .replace('\'', "''");
+                let result = (|| { #body })();
+                // fails in a transaction aborted by the query, whose rollback restores the setting
+                let restored = client.batch_execute(&format!("SET statement_timeout = '{}'", previous));
+                result.and_then(|value| restored.map(|()| value))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timeout_units() {
+        assert_eq!(parse_timeout("250"), Some(Some(250)));
+        assert_eq!(parse_timeout("250ms"), Some(Some(250)));
+        assert_eq!(parse_timeout(" 2 s "), Some(Some(2_000)));
+        assert_eq!(parse_timeout("3m"), Some(Some(180_000)));
+        assert_eq!(parse_timeout("3min"), Some(Some(180_000)));
+        assert_eq!(parse_timeout("0s"), Some(None));
+    }
+
+    #[test]
+    fn invalid_timeouts() {
+        assert_eq!(parse_timeout(""), None);
+        assert_eq!(parse_timeout("s"), None);
+        assert_eq!(parse_timeout("1h"), None);
+        assert_eq!(parse_timeout("-1s"), None);
+        assert_eq!(parse_timeout("1.5s"), None);
+        assert_eq!(parse_timeout("18446744073709551615m"), None);
+    }
+}
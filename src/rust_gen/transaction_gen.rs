@@ -0,0 +1,181 @@
+use crate::db_support::DbCrate;
+use crate::directive::Visibility;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+/// `run_in_transaction` generator retrying serialization failures and deadlocks
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresTransactionGen {
+    db_crate: DbCrate,
+    visibility: Visibility,
+    /// Implement `TransactionError` for the generated `QueryError`
+    query_error: bool,
+}
+
+impl PostgresTransactionGen {
+    pub(crate) fn new(db_crate: DbCrate, visibility: Visibility, query_error: bool) -> Self {
+        Self {
+            db_crate,
+            visibility,
+            query_error,
+        }
+    }
+
+    pub(crate) fn generate(&self) -> TokenStream {
+        let error_ident = self.db_crate.error_ident();
+        let sqlstate_ident = self.db_crate.sqlstate_ident();
+        let isolation_level_ident = self.db_crate.isolation_level_ident();
+        let visibility = self.visibility;
+        let run_in_transaction = self.generate_run_in_transaction();
+
+        let query_error_impl = if self.query_error {
+            quote! {
+                impl TransactionError for QueryError {
+                    fn is_retryable(&self) -> bool {
+                        QueryError::is_retryable(self)
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            /// Errors returned from a transaction closure
+            #visibility trait TransactionError: From<#error_ident> {
+                /// Whether the transaction may succeed when retried
+                fn is_retryable(&self) -> bool;
+            }
+
+            impl TransactionError for #error_ident {
+                fn is_retryable(&self) -> bool {
+                    self.code().is_some_and(|code| {
+                        *code == #sqlstate_ident::T_R_SERIALIZATION_FAILURE
+                            || *code == #sqlstate_ident::T_R_DEADLOCK_DETECTED
+                    })
+                }
+            }
+
+            #query_error_impl
+
+            /// Options for `run_in_transaction`
+            #[derive(Debug, Clone)]
+            #visibility struct TransactionOptions {
+                /// Isolation level, or the server default when `None`
+                pub isolation_level: Option<#isolation_level_ident>,
+                pub read_only: bool,
+                /// Number of retries after the first attempt
+                pub max_retries: u32,
+                /// Delay before the first retry, doubled on every further retry
+                pub initial_backoff: std::time::Duration,
+                pub max_backoff: std::time::Duration,
+            }
+
+            impl Default for TransactionOptions {
+                fn default() -> Self {
+                    Self {
+                        isolation_level: None,
+                        read_only: false,
+                        max_retries: 3,
+                        initial_backoff: std::time::Duration::from_millis(10),
+                        max_backoff: std::time::Duration::from_secs(1),
+                    }
+                }
+            }
+
+            #run_in_transaction
+        }
+    }
+
+    fn generate_run_in_transaction(&self) -> TokenStream {
+        let visibility = self.visibility;
+        let connection_ident = self.db_crate.connection_ident();
+        let transaction_ident = self.db_crate.transaction_ident();
+
+        let begin = quote! {
+            let mut builder = client.build_transaction().read_only(options.read_only);
+            if let Some(isolation_level) = options.isolation_level {
+                builder = builder.isolation_level(isolation_level);
+            }
+        };
+        let retry = quote! {
+            match result {
+                Err(err) if err.is_retryable() && attempt < options.max_retries => {
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        };
+
+        match self.db_crate {
+            DbCrate::TokioPostgres | DbCrate::DeadPoolPostgres => quote! {
+                /// Runs `f` in a transaction, retrying on serialization failures and deadlocks
+                ///
+                /// The transaction is committed when `f` succeeds and rolled back otherwise.
+                #visibility async fn run_in_transaction<T, E, F>(
+                    client: &mut #connection_ident,
+                    options: &TransactionOptions,
+                    mut f: F,
+                ) -> Result<T, E>
+                where
+                    E: TransactionError,
+                    F: for<'t> FnMut(
+                        &'t #transaction_ident<'_>,
+                    ) -> std::pin::Pin<
+                        Box<dyn std::future::Future<Output = Result<T, E>> + Send + 't>,
+                    >,
+                {
+                    let mut attempt = 0;
+                    let mut backoff = options.initial_backoff;
+                    loop {
+                        if attempt > 0 {
+                            tokio::time::sleep(backoff).await;
+                            backoff = backoff.saturating_mul(2).min(options.max_backoff);
+                        }
+                        #begin
+                        let result = match builder.start().await {
+                            Ok(transaction) => match f(&transaction).await {
+                                Ok(value) => transaction.commit().await.map(|_| value).map_err(E::from),
+                                Err(err) => Err(err),
+                            },
+                            Err(err) => Err(E::from(err)),
+                        };
+                        #retry
+                    }
+                }
+            },
+            DbCrate::Postgres => quote! {
+                /// Runs `f` in a transaction, retrying on serialization failures and deadlocks
+                ///
+                /// The transaction is committed when `f` succeeds and rolled back otherwise.
+                #visibility fn run_in_transaction<T, E, F>(
+                    client: &mut #connection_ident,
+                    options: &TransactionOptions,
+                    mut f: F,
+                ) -> Result<T, E>
+                where
+                    E: TransactionError,
+                    F: FnMut(&mut #transaction_ident<'_>) -> Result<T, E>,
+                {
+                    let mut attempt = 0;
+                    let mut backoff = options.initial_backoff;
+                    loop {
+                        if attempt > 0 {
+                            std::thread::sleep(backoff);
+                            backoff = backoff.saturating_mul(2).min(options.max_backoff);
+                        }
+                        #begin
+                        let result = match builder.start() {
+                            Ok(mut transaction) => match f(&mut transaction) {
+                                Ok(value) => transaction.commit().map(|_| value).map_err(E::from),
+                                Err(err) => Err(err),
+                            },
+                            Err(err) => Err(E::from(err)),
+                        };
+                        #retry
+                    }
+                }
+            },
+        }
+    }
+}
@@ -1,56 +1,273 @@
 use std::{borrow::Cow, collections::BTreeMap};
 
+use crate::directive::Visibility;
+use crate::rust_gen::attribute_gen::AttributeMap;
+use crate::rust_gen::serde_gen::SerdeOptions;
+use crate::utils::Renames;
 use crate::{plugin, utils};
-use proc_macro2::{Literal, Span};
+use proc_macro2::Literal;
 use quote::{ToTokens, quote};
 use syn::Ident;
 
 pub(crate) trait GenericEnum {
     fn ident_str(&self) -> String;
     fn ident(&self) -> Ident {
-        Ident::new(&self.ident_str(), Span::call_site())
+        utils::rust_ident(&self.ident_str())
     }
 }
 
+/// Options of the generated enums
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct EnumGenOptions {
+    /// `ALL`, `as_str`, `FromStr` and `Display`
+    pub(crate) emit_helpers: bool,
+    /// `Unknown(String)` variant with hand-written `FromSql` and `ToSql`
+    pub(crate) unknown_variant: bool,
+    /// `serde` derives with the DB labels
+    pub(crate) serde: SerdeOptions,
+    pub(crate) visibility: Visibility,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct PostgresEnum {
     name: String,
-    values: Vec<proc_macro2::TokenStream>,
+    /// Rust name built from the renamed DB name
+    rust_name: String,
+    comment: String,
+    /// DB label and Rust variant of each value
+    values: Vec<(String, Ident)>,
+    options: EnumGenOptions,
 }
 
 impl PostgresEnum {
-    pub(crate) fn new(catalog_enum: &plugin::Enum) -> Self {
+    const UNKNOWN_VARIANT: &str = "Unknown";
+
+    pub(crate) fn new(
+        catalog_enum: &plugin::Enum,
+        options: EnumGenOptions,
+        renames: &Renames,
+    ) -> crate::Result<Self> {
         let name = catalog_enum.name.clone();
+        let rust_name = utils::rust_value_ident(&name, renames);
         let values = catalog_enum
             .vals
             .iter()
             .map(|v| {
-                let original_literal = Literal::string(v);
-                let ident_str = utils::rust_value_ident(v);
-                let rs_ident = Ident::new(&ident_str, Span::call_site());
-                quote! {
-                    #[postgres(name = #original_literal)]
-                    #rs_ident
-                }
+                (
+                    v.clone(),
+                    utils::rust_ident(&utils::rust_value_ident(v, renames)),
+                )
             })
-            .collect();
-        Self { name, values }
+            .collect::<Vec<_>>();
+        if options.unknown_variant {
+            if let Some((label, _)) = values.iter().find(|(_, v)| v == Self::UNKNOWN_VARIANT) {
+                return Err(crate::Error::any_error(format!(
+                    "enum value `{}` of {} conflicts with the `Unknown` variant, rename it with the `rename` option",
+                    label, name
+                )));
+            }
+        }
+        Ok(Self {
+            name,
+            rust_name,
+            comment: catalog_enum.comment.clone(),
+            values,
+            options,
+        })
     }
 
     pub(crate) fn with_derive(
         &self,
         derive: &proc_macro2::TokenStream,
+        attributes: &AttributeMap,
     ) -> proc_macro2::TokenStream {
+        let docs = utils::doc_attrs(self.comment.lines());
+        let definition = if self.options.unknown_variant {
+            self.forward_compatible()
+        } else {
+            self.to_token_stream()
+        };
+        let helpers = if self.options.emit_helpers {
+            self.helpers()
+        } else {
+            quote! {}
+        };
+        let serde_attrs = self.options.serde.enum_attrs();
+        let config_attrs = attributes.enum_attrs(&self.name, &self.ident_str());
         quote! {
+            #docs
             #derive
-            #self
+            #serde_attrs
+            #config_attrs
+            #definition
+            #helpers
         }
     }
+
+    /// `#[non_exhaustive]` enum reading labels added to the DB enum later as `Unknown`
+    ///
+    /// `FromSql` and `ToSql` are implemented by hand because the derives reject such labels.
+    fn forward_compatible(&self) -> proc_macro2::TokenStream {
+        let ident = self.ident();
+        let name = &self.name;
+        let unknown = utils::rust_ident(Self::UNKNOWN_VARIANT);
+        let unknown_attr = self.options.serde.unknown_variant_attr();
+        let variants = self.values.iter().map(|(label, variant)| {
+            let serde_attr = self.options.serde.variant_attr(label);
+            quote! {
+                #serde_attr
+                #variant
+            }
+        });
+        let from_sql_arms = self
+            .values
+            .iter()
+            .map(|(label, variant)| quote! { #label => Self::#variant });
+        let to_sql_arms = self
+            .values
+            .iter()
+            .map(|(label, variant)| quote! { Self::#variant => #label });
+
+        let visibility = self.options.visibility;
+        quote! {
+            #[non_exhaustive]
+            #visibility enum #ident {
+                #(#variants,)*
+                /// Value added to the DB enum after this code was generated
+                #unknown_attr
+                #unknown(String),
+            }
+
+            impl<'a> postgres_types::FromSql<'a> for #ident {
+                fn from_sql(
+                    _: &postgres_types::Type,
+                    raw: &'a [u8],
+                ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+                    Ok(match std::str::from_utf8(raw)? {
+                        #(#from_sql_arms,)*
+                        label => Self::#unknown(label.to_string()),
+                    })
+                }
+
+                fn accepts(ty: &postgres_types::Type) -> bool {
+                    ty.name() == #name && matches!(ty.kind(), postgres_types::Kind::Enum(_))
+                }
+            }
+
+            impl postgres_types::ToSql for #ident {
+                fn to_sql(
+                    &self,
+                    _: &postgres_types::Type,
+                    out: &mut postgres_types::private::BytesMut,
+                ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+                    let label = match self {
+                        #(#to_sql_arms,)*
+                        Self::#unknown(label) => label,
+                    };
+                    out.extend_from_slice(label.as_bytes());
+                    Ok(postgres_types::IsNull::No)
+                }
+
+                fn accepts(ty: &postgres_types::Type) -> bool {
+                    <Self as postgres_types::FromSql>::accepts(ty)
+                }
+
+                postgres_types::to_sql_checked!();
+            }
+        }
+    }
+
+    /// `ALL`, `as_str`, `Display` and `FromStr` using the DB labels
+    fn helpers(&self) -> proc_macro2::TokenStream {
+        let ident = self.ident();
+        let type_name = ident.to_string();
+        let variants = self.values.iter().map(|(_, variant)| variant);
+        let as_str_arms = self
+            .values
+            .iter()
+            .map(|(label, variant)| quote! { Self::#variant => #label });
+        let from_str_arms = self
+            .values
+            .iter()
+            .map(|(label, variant)| quote! { #label => Ok(Self::#variant) });
+        // `Unknown` borrows its label, and holds any other label parsed
+        let (label_type, unknown_arm, parse_unknown) = if self.options.unknown_variant {
+            let unknown = utils::rust_ident(Self::UNKNOWN_VARIANT);
+            (
+                quote! { &str },
+                quote! { Self::#unknown(label) => label, },
+                quote! { _ => Ok(Self::#unknown(s.to_string())), },
+            )
+        } else {
+            (
+                quote! { &'static str },
+                quote! {},
+                quote! {
+                    _ => Err(ParseEnumError {
+                        type_name: #type_name,
+                        value: s.to_string(),
+                    }),
+                },
+            )
+        };
+
+        quote! {
+            impl #ident {
+                /// Every value in the order of the DB enum
+                pub const ALL: &'static [Self] = &[#(Self::#variants),*];
+
+                /// DB label of the value
+                pub fn as_str(&self) -> #label_type {
+                    match self {
+                        #(#as_str_arms,)*
+                        #unknown_arm
+                    }
+                }
+            }
+
+            impl std::fmt::Display for #ident {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    f.write_str(self.as_str())
+                }
+            }
+
+            impl std::str::FromStr for #ident {
+                type Err = ParseEnumError;
+
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    match s {
+                        #(#from_str_arms,)*
+                        #parse_unknown
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Generates the `ParseEnumError` returned by the `FromStr` of every enum
+pub(crate) fn generate_parse_enum_error(visibility: Visibility) -> proc_macro2::TokenStream {
+    quote! {
+        /// Error parsing an enum from a string which is not one of its DB labels
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #visibility struct ParseEnumError {
+            pub type_name: &'static str,
+            pub value: String,
+        }
+
+        impl std::fmt::Display for ParseEnumError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "invalid {} value: `{}`", self.type_name, self.value)
+            }
+        }
+
+        impl std::error::Error for ParseEnumError {}
+    }
 }
 
 impl GenericEnum for PostgresEnum {
     fn ident_str(&self) -> String {
-        utils::rust_value_ident(&self.name)
+        self.rust_name.clone()
     }
 }
 
@@ -58,11 +275,20 @@ impl ToTokens for PostgresEnum {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let original_literal = Literal::string(&self.name);
         let ident = self.ident();
-        let variants = &self.values;
+        let variants = self.values.iter().map(|(label, variant)| {
+            let serde_attr = self.options.serde.variant_attr(label);
+            let label = Literal::string(label);
+            quote! {
+                #[postgres(name = #label)]
+                #serde_attr
+                #variant
+            }
+        });
 
+        let visibility = self.options.visibility;
         let tt = quote! {
             #[postgres(name = #original_literal)]
-            pub enum #ident {
+            #visibility enum #ident {
                 #(#variants),*
             }
         };
@@ -111,10 +337,54 @@ pub(crate) fn col_type(ident: &plugin::Identifier) -> String {
     }
 }
 
+/// Returns the `postgres_types::Type` constant name of a built-in column type
+///
+/// Enums, composites and extension types are not built-in and return `None`.
+pub(crate) fn builtin_type_const(db_type: &str) -> Option<&'static str> {
+    let name = db_type.strip_prefix("pg_catalog.").unwrap_or(db_type);
+    let type_const = match name {
+        "bool" | "boolean" => "BOOL",
+        r#""char""# => "CHAR",
+        "int2" | "smallint" | "serial2" | "smallserial" => "INT2",
+        "int4" | "int" | "integer" | "serial4" | "serial" => "INT4",
+        "int8" | "bigint" | "serial8" | "bigserial" => "INT8",
+        "oid" => "OID",
+        "float4" | "real" => "FLOAT4",
+        "float8" | "float" | "double precision" => "FLOAT8",
+        "numeric" | "decimal" => "NUMERIC",
+        "money" => "MONEY",
+        "text" | "string" => "TEXT",
+        "varchar" => "VARCHAR",
+        "bpchar" => "BPCHAR",
+        "name" => "NAME",
+        "bytea" | "blob" => "BYTEA",
+        "date" => "DATE",
+        "time" => "TIME",
+        "timetz" => "TIMETZ",
+        "timestamp" => "TIMESTAMP",
+        "timestamptz" => "TIMESTAMPTZ",
+        "interval" => "INTERVAL",
+        "inet" => "INET",
+        "cidr" => "CIDR",
+        "macaddr" => "MACADDR",
+        "json" => "JSON",
+        "jsonb" => "JSONB",
+        "uuid" => "UUID",
+        _ => return None,
+    };
+    Some(type_const)
+}
+
 pub(crate) trait TypeMap {
     fn get(&self, column_type: &str) -> crate::Result<&syn::TypePath>;
     fn add(&mut self, db_type: &str, rs_type: &str) -> crate::Result<()>;
     fn is_copy_cheap_type(&self, rs_type: &str) -> bool;
+    /// Whether values of the type can be compared with `PartialEq`
+    fn is_partial_eq_type(&self, rs_type: &str) -> bool;
+    /// Comment of a catalog table, used as documentation
+    fn table_comment(&self, table: &str) -> Option<&str>;
+    /// `rename` option applied to the DB names idents are built from
+    fn renames(&self) -> &Renames;
 }
 
 #[derive(Default)]
@@ -122,6 +392,10 @@ pub(crate) struct PgTypeMap {
     m: BTreeMap<String, syn::TypePath>,
     enum_types: std::collections::HashSet<String>,
     copy_types: std::collections::HashSet<String>,
+    /// Types known to implement `PartialEq`, keyed by their token string
+    partial_eq_types: std::collections::HashSet<String>,
+    table_comments: std::collections::HashMap<String, String>,
+    renames: Renames,
 }
 
 impl TypeMap for PgTypeMap {
@@ -138,6 +412,18 @@ impl TypeMap for PgTypeMap {
         Ok(())
     }
 
+    fn table_comment(&self, table: &str) -> Option<&str> {
+        self.table_comments.get(table).map(String::as_str)
+    }
+
+    fn renames(&self) -> &Renames {
+        &self.renames
+    }
+
+    fn is_partial_eq_type(&self, rs_type: &str) -> bool {
+        self.partial_eq_types.contains(rs_type)
+    }
+
     fn is_copy_cheap_type(&self, rs_type: &str) -> bool {
         // Check if it's a DB-generated enum
         if self.enum_types.contains(rs_type) {
@@ -158,19 +444,24 @@ impl TypeMap for PgTypeMap {
 }
 
 impl PgTypeMap {
-    pub(crate) fn new(catalog: &plugin::Catalog) -> crate::Result<Self> {
+    pub(crate) fn new(catalog: &plugin::Catalog, renames: Renames) -> crate::Result<Self> {
         let mut type_map = Self::initialize()?;
-        for pg_enum in catalog
-            .schemas
-            .iter()
-            .flat_map(|s| s.enums.as_slice())
-            .map(PostgresEnum::new)
-        {
+        type_map.renames = renames;
+        for catalog_enum in catalog.schemas.iter().flat_map(|s| s.enums.as_slice()) {
+            let pg_enum =
+                PostgresEnum::new(catalog_enum, EnumGenOptions::default(), &type_map.renames)?;
             let ident = pg_enum.ident_str();
             type_map.add(&pg_enum.name, &ident)?;
             // Track DB-generated enums as copy-cheap types
             type_map.enum_types.insert(ident);
         }
+        type_map.table_comments = catalog
+            .schemas
+            .iter()
+            .flat_map(|s| s.tables.as_slice())
+            .filter(|table| !table.comment.is_empty())
+            .filter_map(|table| Some((table.rel.as_ref()?.name.clone(), table.comment.clone())))
+            .collect();
         Ok(type_map)
     }
 
@@ -178,6 +469,21 @@ impl PgTypeMap {
         self.copy_types.insert(rs_type.to_string());
     }
 
+    /// Marks `rs_type`, e.g. an `overrides` type, as implementing `PartialEq`
+    pub(crate) fn add_partial_eq_type(&mut self, rs_type: &str) -> crate::Result<()> {
+        let path = syn::parse_str::<syn::TypePath>(rs_type)
+            .map_err(|_| crate::Error::invalid_rust_type(rs_type))?;
+        self.partial_eq_types
+            .insert(path.to_token_stream().to_string());
+        Ok(())
+    }
+
+    /// Marks the DB-generated enums as implementing `PartialEq`
+    pub(crate) fn add_partial_eq_enums(&mut self) {
+        self.partial_eq_types
+            .extend(self.enum_types.iter().cloned());
+    }
+
     fn initialize() -> crate::Result<Self> {
         // Map sqlc type and Rust type
         // - https://github.com/sqlc-dev/sqlc/blob/v1.28.0/internal/codegen/golang/postgresql_type.go#L37
@@ -284,6 +590,8 @@ impl PgTypeMap {
             for pg in pg_types {
                 type_map.add(&pg.to_string(), rs_type)?;
             }
+            // every built-in type implements `PartialEq`
+            type_map.add_partial_eq_type(rs_type)?;
         }
 
         Ok(type_map)
@@ -1,11 +1,39 @@
-use convert_case::{Case, Casing};
+use convert_case::{Boundary, Case, Casing};
+use proc_macro2::{Ident, Span};
 use regex_lite::Regex;
+use std::collections::BTreeMap;
 use std::sync::LazyLock;
 
-fn normalize_str(value: &str) -> String {
+/// Strict and reserved keywords, which need a raw identifier like `r#type`
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// `rename` option mapping DB names (tables, columns, enum values and queries) to the names
+/// used for Rust idents
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Renames(BTreeMap<String, String>);
+
+impl Renames {
+    pub(crate) fn new(renames: BTreeMap<String, String>) -> Self {
+        Self(renames)
+    }
+
+    /// Name the ident of the DB name `value` is built from
+    fn get<'a>(&'a self, value: &'a str) -> &'a str {
+        self.0.get(value).map(String::as_str).unwrap_or(value)
+    }
+}
+
+fn normalize_str(value: &str, renames: &Renames) -> String {
     static IDENT_PATTERN: LazyLock<Regex> =
         LazyLock::new(|| Regex::new(r#"[^a-zA-Z0-9_]"#).unwrap());
 
+    let value = renames.get(value);
     let value = value.replace("-", "_");
     let value = value.replace(":", "_");
     let value = value.replace("/", "_");
@@ -13,22 +41,99 @@ fn normalize_str(value: &str) -> String {
     value.to_string()
 }
 
-/// convert str to valid rust const ident
-pub(crate) fn rust_const_ident(value: &str) -> String {
-    normalize_str(value).to_case(Case::UpperSnake)
+/// convert `value` to `case`, prefixing names starting with a digit like enum value `1st`,
+/// which are not valid idents
+///
+/// The leading digits stay in the word they start, so `1st` becomes `_1st` rather than `_1St`.
+fn convert_ident(value: String, case: Case) -> String {
+    if value.starts_with(|c: char| c.is_ascii_digit()) {
+        let value = value
+            .without_boundaries(&Boundary::digit_letter())
+            .to_case(case);
+        format!("_{}", value)
+    } else {
+        value.to_case(case)
+    }
+}
+
+/// convert the DB name `value` to valid rust const ident, applying `renames` first
+pub(crate) fn rust_const_ident(value: &str, renames: &Renames) -> String {
+    convert_ident(normalize_str(value, renames), Case::UpperSnake)
+}
+
+/// convert the DB name `value` to valid rust ident like struct, enum, enum value, applying
+/// `renames` first
+pub(crate) fn rust_value_ident(value: &str, renames: &Renames) -> String {
+    convert_ident(normalize_str(value, renames), Case::Pascal)
+}
+
+/// convert the DB name `value` to valid rust struct field, applying `renames` first
+pub(crate) fn rust_struct_field(value: &str, renames: &Renames) -> String {
+    convert_ident(normalize_str(value, renames), Case::Snake)
+}
+
+/// convert the DB name `value` to valid fn ident, applying `renames` first
+pub(crate) fn rust_fn_ident(value: &str, renames: &Renames) -> String {
+    convert_ident(normalize_str(value, renames), Case::Snake)
 }
 
-/// convert str to valid rust ident like struct, enum, enum value
-pub(crate) fn rust_value_ident(value: &str) -> String {
-    normalize_str(value).to_case(Case::Pascal)
+/// convert an ident str to `Ident`, escaping keywords
+///
+/// Keywords become raw identifiers (`r#type`). `self`, `Self`, `super` and `crate` cannot be
+/// raw and get a `_` suffix instead.
+pub(crate) fn rust_ident(value: &str) -> Ident {
+    match value {
+        "self" | "Self" | "super" | "crate" => {
+            Ident::new(&format!("{}_", value), Span::call_site())
+        }
+        _ if RUST_KEYWORDS.contains(&value) => Ident::new_raw(value, Span::call_site()),
+        _ => Ident::new(value, Span::call_site()),
+    }
 }
 
-/// convert str to valid rust struct field
-pub(crate) fn rust_struct_field(value: &str) -> String {
-    normalize_str(value).to_case(Case::Snake)
+/// convert text lines to `#[doc]` attributes rendered as `///` comments
+pub(crate) fn doc_attrs<'a>(lines: impl IntoIterator<Item = &'a str>) -> proc_macro2::TokenStream {
+    lines
+        .into_iter()
+        .map(|line| {
+            let line = line.trim_end();
+            let line = if line.is_empty() || line.starts_with(' ') {
+                line.to_string()
+            } else {
+                format!(" {}", line)
+            };
+            quote::quote! { #[doc = #line] }
+        })
+        .collect()
 }
 
-/// convert str to valid fn ident
-pub(crate) fn rust_fn_ident(value: &str) -> String {
-    normalize_str(value).to_case(Case::Snake)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leading_digits_are_escaped() {
+        let renames = Renames::default();
+        assert_eq!(rust_value_ident("1st", &renames), "_1st");
+        assert_eq!(rust_struct_field("1st", &renames), "_1st");
+        assert_eq!(rust_const_ident("1st", &renames), "_1ST");
+        assert_eq!(rust_value_ident("2nd_place", &renames), "_2ndPlace");
+        assert_eq!(rust_fn_ident("2nd_place", &renames), "_2nd_place");
+        assert_eq!(rust_struct_field("1_st", &renames), "_1_st");
+    }
+
+    #[test]
+    fn inner_digits_are_split() {
+        let renames = Renames::default();
+        assert_eq!(rust_struct_field("top10", &renames), "top_10");
+        assert_eq!(rust_value_ident("area51_zone", &renames), "Area51Zone");
+    }
+
+    #[test]
+    fn keywords_are_escaped() {
+        assert_eq!(rust_ident("type").to_string(), "r#type");
+        assert_eq!(rust_ident("self").to_string(), "self_");
+        assert_eq!(rust_ident("Self").to_string(), "Self_");
+        assert_eq!(rust_ident("name").to_string(), "name");
+    }
 }